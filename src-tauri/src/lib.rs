@@ -1,25 +1,309 @@
 mod library;
 
-use std::fs::{read_to_string};
-use tauri::{AppHandle, Manager};
-use tauri::path::BaseDirectory;
-use log::{debug, info};
+use tauri::{AppHandle, Emitter, Manager, RunEvent};
+use tauri::ipc::Channel;
+use log::{debug, info, warn};
 
-use library::utils::{read_csv_file, read_type_set, folder_files, logger_setting};
+use library::models::{CsvRecord, ReadOptions, CsvStreamMessage};
+use library::error::CsvError;
+use library::cache::CsvCache;
+use library::watcher::FolderWatcher;
+use library::utils::{read_csv_file, read_csv_file_with_notes, stream_csv_file, arrange_records, shape_records, merged_csv_records, search_csv_records, sort_csv_records, read_type_set, facet_counts, folder_files, load_app_config, document_directory, config_directory, logger_setting, change_log_level, inconsistent_delimiter_lines, example_prefix_violations, csv_template_header, write_csv_template, tag_pair_counts, export_wide_csv, export_subset_csv, canonicalize_csv, merge_csv_columns, upgrade_https_csv, drop_empty_csv_columns, distinct_values_where, benchmark_csv_parse, read_csv_file_with_rules, duplicate_key_groups, peek_csv_file, csv_file_metadata, non_https_urls, missing_names, summarize_csv_file, read_change_log_entries, empty_csv_columns, check_csv_row_count, csv_to_geojson, diff_folder_files, clear_csv_cache_entries, row_text_lengths, read_csv_columns, tag_limit_violations, distinct_values_page, forbidden_term_matches, read_forbidden_terms, export_xlsx_category_sheets, export_json_records, normalize_csv_urls, records_fingerprint_hash, profile_records, duplicate_header_names, flatten_records, validate_csv_file, validate_folder_files, paginate, read_config_file, read_csv_dynamic_rows, filter_csv_records, write_csv_records};
+
+/// 把 CsvError 轉換成錯誤回應 (error 為錯誤訊息，另外帶有 kind 與行號等欄位)
+/// - 例如: {"error": "line 12: invalid digit found in string", "kind": "parse", "line": 12, "message": "invalid digit found in string"}
+/// ## 參數
+/// - `error`: 讀取 CSV 時的錯誤
+/// ## 返回
+/// - `String`: 錯誤回應的 JSON 字符串
+fn csv_error_response(error: &CsvError) -> String {
+
+    let mut response = serde_json::json!({ "error": error.to_string() });
+    if let (Some(response), Ok(serde_json::Value::Object(details))) = (response.as_object_mut(), serde_json::to_value(error)) { response.extend(details); }
+
+    response.to_string()
+}
 
 /// 讀取 CSV 檔案並返回記錄
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
 /// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，分頁使用其中的 offset / limit；設定 validate 時另外返回無法解析的 URL 於 warnings)
 /// ## 返回
-/// - `String`: 成功時返回記錄的 JSON 字符串，失敗
+/// - `String`: 成功時返回記錄 (分頁後) 與總筆數 total 的 JSON 字符串，失敗
 #[tauri::command]
-fn read_csv(app: AppHandle, filename: String) -> String {
+fn read_csv(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
 
     info!("Loading CSV file: {}", filename);
     debug!("Loading CSV file: {}", filename);
 
-    let records  = match read_csv_file(app.clone(), filename) {
+    let options = options.unwrap_or_default();
+
+    if !options.columns.is_empty() {
+        return match read_csv_columns(app, filename, &options) {
+            Ok(records) => serde_json::json!({ "total": records.len(), "result": paginate(records, &options) }).to_string(),
+            Err(error) => csv_error_response(&error),
+        };
+    }
+
+    let (records, notes) = match read_csv_file_with_notes(app.clone(), filename, &options) {
+        Ok(result) => result,
+        Err(error) => return csv_error_response(&error),
+    };
+
+    let profile = options.profile.then(|| profile_records(&records));
+    let total = records.len();
+
+    let records = match arrange_records(records, &options) {
+        Ok(records) => paginate(records, &options),
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    let records = match shape_records(&records, &options) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    let mut response = serde_json::json!({ "result": records, "total": total });
+    if let (Some(response), Ok(serde_json::Value::Object(notes))) = (response.as_object_mut(), serde_json::to_value(&notes)) { response.extend(notes); }
+    if let Some(profile) = profile { response["profile"] = serde_json::json!(profile); }
+
+    response.to_string()
+}
+
+/// 讀取多個 CSV 檔案並合併成一份記錄 (URL 相同的記錄只保留第一筆)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filenames`: CSV 檔案的名稱列表，例如: ["tools-a.csv", "tools-b.csv"]
+/// - `options`: 讀取選項 (可省略，與 read_csv 相同)
+/// ## 返回
+/// - `String`: 成功時返回記錄 (分頁後) 與總筆數 total 的 JSON 字符串，失敗 (錯誤訊息帶有失敗的檔名)
+#[tauri::command]
+fn read_csv_merged(app: AppHandle, filenames: Vec<String>, options: Option<ReadOptions>) -> String {
+
+    let options = options.unwrap_or_default();
+
+    let records = match merged_csv_records(app, &filenames, &options) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    let total = records.len();
+
+    let records = match arrange_records(records, &options) {
+        Ok(records) => paginate(records, &options),
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    let records = match shape_records(&records, &options) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": records, "total": total }).to_string()
+}
+
+/// 搜尋 Name / Notes / URL 包含關鍵字的記錄 (不分大小寫)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `query`: 關鍵字 (空字串時返回全部記錄)
+/// - `include_tags`: 是否也比對多值欄位的每個值，例如: 搜尋某個 Language (可省略，預設 false)
+/// ## 返回
+/// - `String`: 成功時返回符合記錄的 JSON 字符串 (與 read_csv 的 result 相同)，失敗
+#[tauri::command]
+fn search_csv(app: AppHandle, filename: String, query: String, include_tags: Option<bool>) -> String {
+
+    let records = match search_csv_records(app, filename, &query, include_tags.unwrap_or_default()) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": records }).to_string()
+}
+
+/// 讀取 CSV 檔案並依欄位排序記錄 (穩定排序)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 欄位名稱，不分大小寫 (Name / Level / URL / Notes / Example，Level 依數字排序)
+/// - `descending`: 是否由大到小排序 (可省略，預設 false)
+/// ## 返回
+/// - `String`: 成功時返回排序後記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn sort_csv(app: AppHandle, filename: String, column: String, descending: Option<bool>) -> String {
+
+    let records = match sort_csv_records(app, filename, &column, descending.unwrap_or_default()) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": records }).to_string()
+}
+
+/// 讀取 CSV 檔案並同時返回每個欄位的統計資料 (等同於 read_csv 加上 profile 選項)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回記錄與 profile (欄位名稱 => 統計資料) 的 JSON 字符串，失敗
+#[tauri::command]
+fn read_csv_profiled(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+    let options = ReadOptions { profile: true, ..options.unwrap_or_default() };
+    read_csv(app, filename, Some(options))
+}
+
+/// 讀取 CSV 檔案並把每筆記錄攤平成只有純量值的物件 (陣列元素以「欄位.索引」為鍵，例如: Platform.0)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回攤平後記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn read_csv_flat(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let options = options.unwrap_or_default();
+    let records = match read_csv_file(app, filename, &options) {
+        Ok(records) => records,
+        Err(error) => return csv_error_response(&error),
+    };
+
+    let records = match arrange_records(records, &options).and_then(|records| shape_records(&records, &options)) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": flatten_records(records) }).to_string()
+}
+
+/// 讀取任意欄位的 CSV 檔案 (不限定 CsvRecord 的欄位，值都是字串)，並返回偵測到的標題列
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / duplicateHeaders / offset / limit)
+/// ## 返回
+/// - `String`: 成功時返回記錄、標題列 headers (欄位順序) 與總筆數 total 的 JSON 字符串，失敗
+#[tauri::command]
+fn read_csv_dynamic(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let options = options.unwrap_or_default();
+    let (headers, rows) = match read_csv_dynamic_rows(app, filename, &options) {
+        Ok(result) => result,
+        Err(error) => return csv_error_response(&error),
+    };
+
+    let total = rows.len();
+    serde_json::json!({ "result": paginate(rows, &options), "headers": headers, "total": total }).to_string()
+}
+
+/// 讀取多值欄位與指定值有交集的記錄 (不分大小寫，values 為空陣列時返回全部)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `field`: 多值欄位名稱 (Platform / Type / OS / Language / Category)
+/// - `values`: 要包含的值，例如: ["CLI", "GUI"]
+/// - `filters_json`: 其他欄位的篩選條件，例如: {"type": {"exclude": ["Deprecated"]}} (可省略)
+/// ## 返回
+/// - `String`: 成功時返回記錄的 JSON 字符串 (與 read_csv 相同格式)，失敗
+#[tauri::command]
+fn filter_csv(app: AppHandle, filename: String, field: String, values: Vec<String>, filters_json: Option<String>) -> String {
+
+    let options = ReadOptions::default();
+    let records = match filter_csv_records(app, filename, field, values, filters_json.as_deref().unwrap_or_default()).and_then(|records| shape_records(&records, &options)) {
+        Ok(records) => records,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": records }).to_string()
+}
+
+/// read_csv_stream / stream_csv 預設每批傳送的記錄數
+const STREAM_BATCH_SIZE: usize = 100;
+
+/// 逐批讀取 CSV 檔案並透過 Channel 傳給前端 (最後傳送 done 與總筆數)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `channel`: 前端建立的 Channel
+/// - `batch_size`: 每批的記錄數 (可省略，預設 100)
+/// ## 返回
+/// - `String`: 成功時返回總筆數的 JSON 字符串，失敗
+#[tauri::command]
+async fn read_csv_stream(app: AppHandle, filename: String, channel: Channel<CsvStreamMessage>, batch_size: Option<usize>) -> String {
+
+    let batch_size = batch_size.unwrap_or(STREAM_BATCH_SIZE);
+    let total = match stream_csv_file(app.clone(), filename, batch_size, |records| {
+        channel.send(CsvStreamMessage::Records { records }).map_err(|error| std::io::Error::other(error.to_string()))
+    }) {
+        Ok(total) => total,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    if let Err(error) = channel.send(CsvStreamMessage::Done { total }) {
+        return serde_json::json!({ "error": error.to_string() }).to_string();
+    }
+
+    serde_json::json!({ "result": { "total": total } }).to_string()
+}
+
+/// stream_csv 每批記錄的事件名稱
+const CSV_CHUNK_EVENT: &str = "csv-chunk";
+
+/// stream_csv 讀取完成的事件名稱
+const CSV_DONE_EVENT: &str = "csv-done";
+
+/// stream_csv 讀取失敗的事件名稱
+const CSV_ERROR_EVENT: &str = "csv-error";
+
+/// 逐批讀取 CSV 檔案並以 Tauri 事件傳給前端 (不需要建立 Channel，不會產生一個巨大的 JSON 字串)
+/// - csv-chunk: {"filename": ..., "records": [...]}
+/// - csv-done: {"filename": ..., "total": 142}
+/// - csv-error: {"filename": ..., "error": "row 12: ...", "kind": "parse", "line": 13, "row": 12, ...} (之前已送出的批次仍然有效)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `chunk_size`: 每批的記錄數 (可省略，預設 100)
+/// ## 返回
+/// - `String`: 成功時返回總筆數的 JSON 字符串，失敗
+#[tauri::command]
+async fn stream_csv(app: AppHandle, filename: String, chunk_size: Option<usize>) -> String {
+
+    let chunk_size = chunk_size.unwrap_or(STREAM_BATCH_SIZE);
+    let result = stream_csv_file(app.clone(), filename.clone(), chunk_size, |records| {
+        app.emit(CSV_CHUNK_EVENT, serde_json::json!({ "filename": filename, "records": records })).map_err(|error| std::io::Error::other(error.to_string()))
+    });
+
+    let total = match result {
+        Ok(total) => total,
+        Err(error) => {
+            let mut payload = serde_json::json!({ "filename": filename, "error": error.to_string() });
+            if let (Some(payload), Ok(serde_json::Value::Object(details))) = (payload.as_object_mut(), serde_json::to_value(&error)) { payload.extend(details); }
+            if let Err(error) = app.emit(CSV_ERROR_EVENT, payload) { warn!("Failed to emit {}: {}", CSV_ERROR_EVENT, error); }
+
+            return csv_error_response(&error);
+        }
+    };
+
+    if let Err(error) = app.emit(CSV_DONE_EVENT, serde_json::json!({ "filename": filename, "total": total })) {
+        return serde_json::json!({ "error": error.to_string() }).to_string();
+    }
+
+    serde_json::json!({ "result": { "total": total } }).to_string()
+}
+
+/// 依 config 資料夾內的規則檔讀取符合條件的記錄
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `rules_filename`: 規則檔的名稱，例如: "rules.json"
+/// ## 返回
+/// - `String`: 成功時返回記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn read_csv_with_rules(app: AppHandle, filename: String, rules_filename: String) -> String {
+
+    let records = match read_csv_file_with_rules(app.clone(), filename, rules_filename) {
         Ok(records) => records,
         Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
     };
@@ -27,16 +311,38 @@ fn read_csv(app: AppHandle, filename: String) -> String {
     serde_json::json!({ "result": records }).to_string()
 }
 
-/// 取得總Type的數值 => HashSet
+/// 把含有經緯度欄位的 CSV 檔案轉成 GeoJSON FeatureCollection (地圖顯示用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `lat_col`: 緯度欄位名稱
+/// - `lng_col`: 經度欄位名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回 FeatureCollection 與被略過行號的 JSON 字符串，失敗
+#[tauri::command]
+fn read_csv_geojson(app: AppHandle, filename: String, lat_col: String, lng_col: String, options: Option<ReadOptions>) -> String {
+
+    let (collection, skipped) = match csv_to_geojson(app.clone(), filename, lat_col, lng_col, &options.unwrap_or_default()) {
+        Ok(result) => result,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": collection, "skipped": skipped }).to_string()
+}
+
+/// 取得總Type的數值 => 排序後的陣列 (其他欄位請使用 read_distinct)
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
 /// - `filename`: CSV 檔案的名稱
+/// - `delimiter`: 欄位分隔符號，例如: "\t" (可省略，預設為逗號)
 /// ## 返回
 /// - `String`: 成功時返回記錄的 JSON 字符串，失敗
 #[tauri::command]
-fn read_type(app: AppHandle, filename: String) -> String {
+fn read_type(app: AppHandle, filename: String, delimiter: Option<String>) -> String {
 
-    let types  = match read_type_set(app.clone(), filename) {
+    let options = ReadOptions { delimiter, ..ReadOptions::default() };
+    let types  = match read_type_set(app.clone(), filename, &options) {
         Ok(types) => types,
         Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
     };
@@ -44,16 +350,138 @@ fn read_type(app: AppHandle, filename: String) -> String {
     serde_json::json!({ "result": types }).to_string()
 }
 
-/// 讀取 CSV 檔案資料夾檔名列表
+/// 取得每個 Type 出現在幾筆記錄 (其他多值欄位請使用 read_facet_counts)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `delimiter`: 欄位分隔符號，例如: "\t" (可省略，預設為逗號)
+/// ## 返回
+/// - `String`: 成功時返回 Type => 記錄數的 JSON 字符串，失敗
+#[tauri::command]
+fn read_type_counts(app: AppHandle, filename: String, delimiter: Option<String>) -> String {
+    read_facet_counts(app, filename, String::from("type"), delimiter)
+}
+
+/// 取得多值欄位每個值出現在幾筆記錄 (同一筆記錄內重複的值只算一次)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱，不分大小寫 (Platform / Type / OS / Language / Category)
+/// - `delimiter`: 欄位分隔符號，例如: "\t" (可省略，預設為逗號)
+/// ## 返回
+/// - `String`: 成功時返回 值 => 記錄數的 JSON 字符串，失敗
+#[tauri::command]
+fn read_facet_counts(app: AppHandle, filename: String, column: String, delimiter: Option<String>) -> String {
+
+    let options = ReadOptions { delimiter, ..ReadOptions::default() };
+    let counts = match facet_counts(app, filename, &column, &options) {
+        Ok(counts) => counts,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": counts }).to_string()
+}
+
+/// 取得每筆記錄的文字長度 (Unicode 字元數，供虛擬列表預估項目大小)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回與記錄順序相同的字元數陣列的 JSON 字符串，失敗
+#[tauri::command]
+fn row_sizes(app: AppHandle, filename: String) -> String {
+
+    let sizes = match row_text_lengths(app, filename) {
+        Ok(sizes) => sizes,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": sizes }).to_string()
+}
+
+/// 清除 CSV 快取 (包含解析後的記錄與各欄位的不重複值)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `String`: 成功時返回被清除檔案數的 JSON 字符串
+#[tauri::command]
+fn clear_csv_cache(app: AppHandle) -> String {
+    serde_json::json!({ "result": clear_csv_cache_entries(&app) }).to_string()
+}
+
+/// 取得某欄位排序後的不重複值 (可分頁與前綴篩選，供篩選下拉選單與大量值的延遲載入)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 欄位名稱，不分大小寫 (多值欄位 Platform / Type / OS / Language / Category 或其他 CsvRecord 欄位，未知欄位返回錯誤)
+/// - `offset`: 略過前幾個值 (可省略，預設 0)
+/// - `limit`: 最多返回幾個值 (可省略，預設全部)
+/// - `prefix`: 只返回以此開頭的值，不分大小寫 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回該頁的值與總數的 JSON 字符串，失敗
+#[tauri::command]
+fn read_distinct(app: AppHandle, filename: String, column: String, offset: Option<usize>, limit: Option<usize>, prefix: Option<String>) -> String {
+
+    let page = match distinct_values_page(app, filename, &column, offset.unwrap_or_default(), limit, prefix.as_deref()) {
+        Ok(page) => page,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": page }).to_string()
+}
+
+/// 讀取 CSV 檔案資料夾檔名列表 (不含子資料夾)
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
+/// - `extension`: 只列出此副檔名的檔案 (不分大小寫)，省略時為 "csv"
+/// - `include`: 只列出符合任一 glob 的檔名 (可省略)
+/// - `exclude`: 排除符合任一 glob 的檔名 (可省略)
 /// ## 返回
 /// - `String`: 成功時返回記錄的 JSON 字符串，失敗
 #[tauri::command]
-fn csv_list(app: AppHandle) -> String {
+fn csv_list(app: AppHandle, extension: Option<String>, include: Option<Vec<String>>, exclude: Option<Vec<String>>) -> String {
     
-    let list = match app.path().resolve("document", BaseDirectory::Resource) {
-        Ok(path) => match folder_files(path) {
+    let extension = extension.unwrap_or_else(|| String::from("csv"));
+    let include = include.unwrap_or_default();
+    let exclude = exclude.unwrap_or_default();
+
+    let list = match document_directory(&app) {
+        Ok(path) => match folder_files(path, Some(&extension), &include, &exclude) {
+            Ok(array) => array,
+            Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+        },
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": list }).to_string()
+}
+
+/// 在執行時調整日誌等級
+/// ## 參數
+/// - `level`: 日誌等級 (off / error / warn / info / debug / trace，不分大小寫)
+/// ## 返回
+/// - `String`: 成功時返回新日誌等級的 JSON 字符串，失敗
+#[tauri::command]
+fn set_log_level(level: String) -> String {
+
+    let level = match change_log_level(&level) {
+        Ok(level) => level,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": level.to_string().to_lowercase() }).to_string()
+}
+
+/// 讀取 config 資料夾內的 JSON 檔名列表 (可搭配 read_json_file 使用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `String`: 成功時返回檔名列表的 JSON 字符串，失敗
+#[tauri::command]
+fn json_list(app: AppHandle) -> String {
+
+    let list = match config_directory(&app) {
+        Ok(path) => match folder_files(path, Some("json"), &[], &[]) {
             Ok(array) => array,
             Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
         },
@@ -70,24 +498,664 @@ fn csv_list(app: AppHandle) -> String {
 /// - `String`: 成功時返回記錄的 JSON 字符串，失敗
 #[tauri::command]
 fn read_json_file(app: AppHandle, filename: String) -> Result<String, String> {
-    let file_path = app.path()
-        .resolve("config", BaseDirectory::Resource)
-        .map_err(|e| format!("無法獲取資源目錄: {}", e))?
-        .join(filename);
-    
-    read_to_string(&file_path)
+    read_config_file(&app, filename)
         .map_err(|e| format!("無法讀取檔案: {}", e))
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .setup(|app| {
-            if let Err(error) = logger_setting(app) { eprintln!("Failed to setup logging: {}", error); }
+/// 檢查檔案內是否混用分隔符號 (例如: 部分行用逗號、部分行用分號)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回可疑行號的 JSON 字符串，失敗
+#[tauri::command]
+fn detect_inconsistent_delimiters(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let lines = match inconsistent_delimiter_lines(app.clone(), filename, &options.unwrap_or_default()) {
+        Ok(lines) => lines,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": lines }).to_string()
+}
+
+/// 檢查 Example 是否以規則指定的前綴開頭
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `rule_json`: 規則的 JSON 字串 (Type => 前綴)
+/// ## 返回
+/// - `String`: 成功時返回不符合記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn check_example_prefix(app: AppHandle, filename: String, rule_json: String) -> String {
+
+    let violations = match example_prefix_violations(app.clone(), filename, rule_json) {
+        Ok(violations) => violations,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": violations }).to_string()
+}
+
+/// 取得新建 CSV 檔案用的標題列 (與 CsvRecord 的欄位一致)
+/// ## 返回
+/// - `String`: 成功時返回標題列的 JSON 字符串，失敗
+#[tauri::command]
+fn new_csv_template() -> String {
+
+    let header = match csv_template_header() {
+        Ok(header) => header,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": header }).to_string()
+}
+
+/// 在 document 資料夾建立只有標題列的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果的 JSON 字符串，失敗
+#[tauri::command]
+fn create_csv_template(app: AppHandle, filename: String, dry_run: Option<bool>) -> String {
+
+    let report = match write_csv_template(app.clone(), filename, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 計算同一個多值欄位內兩兩值一起出現的次數 (例如: 標記 X 的記錄也常標記 Y)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱，例如: "type"
+/// - `min_count`: 最少一起出現的記錄數
+/// ## 返回
+/// - `String`: 成功時返回值組合的 JSON 字符串，失敗
+#[tauri::command]
+fn tag_pairs(app: AppHandle, filename: String, column: String, min_count: usize) -> String {
+
+    let pairs = match tag_pair_counts(app.clone(), filename, column, min_count) {
+        Ok(pairs) => pairs,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": pairs }).to_string()
+}
+
+/// 把多值欄位展開成編號欄位 (<Column>1..N) 後輸出成新的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `column`: 要展開的多值欄位名稱，例如: "platform"
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果的 JSON 字符串，失敗
+#[tauri::command]
+fn export_wide(app: AppHandle, filename: String, out_filename: String, column: String, dry_run: Option<bool>) -> String {
+
+    let report = match export_wide_csv(app.clone(), filename, out_filename, column, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 同時篩選欄位與資料列後輸出成新的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `options_json`: 欄位與篩選條件的 JSON 字串，例如: {"columns": ["Name", "URL"], "filters": {"os": ["Linux"]}}
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果 (列數、欄位) 的 JSON 字符串，失敗
+#[tauri::command]
+fn export_subset(app: AppHandle, filename: String, out_filename: String, options_json: String, dry_run: Option<bool>) -> String {
+
+    let report = match export_subset_csv(app.clone(), filename, out_filename, options_json, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 把記錄寫成 document 資料夾內的 CSV 檔案 (例如: 儲存前端修改後的資料)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 輸出 CSV 檔案的名稱
+/// - `records`: 要寫入的記錄 (與 read_csv 返回的格式相同)
+/// - `overwrite`: 檔案已存在時是否覆蓋 (可省略，預設不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果 (最終路徑、列數、欄位) 的 JSON 字符串，失敗
+#[tauri::command]
+fn write_csv(app: AppHandle, filename: String, records: Vec<CsvRecord>, overwrite: Option<bool>, dry_run: Option<bool>) -> String {
+
+    let report = match write_csv_records(app.clone(), filename, &records, overwrite.unwrap_or(false), dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 把 CSV 檔案的記錄輸出成 config 資料夾內的 JSON 檔案 (縮排格式的陣列，可再用 read_json_file 讀取)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 JSON 檔案的名稱
+/// - `overwrite`: 檔案已存在時是否覆蓋 (可省略，預設不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果 (輸出路徑、記錄數、欄位) 的 JSON 字符串，失敗
+#[tauri::command]
+fn export_csv_to_json(app: AppHandle, filename: String, out_filename: String, overwrite: Option<bool>, dry_run: Option<bool>) -> String {
+
+    let report = match export_json_records(app, filename, out_filename, overwrite.unwrap_or(false), dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 依 Category 輸出成 xlsx，每個 Category 一個工作表 (有多個 Category 的記錄會出現在每個相關的工作表)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 xlsx 檔案的名稱
+/// - `overwrite`: 檔案已存在時是否覆蓋 (可省略，預設不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果 (工作表名稱、列數) 的 JSON 字符串，失敗
+#[tauri::command]
+fn export_xlsx_by_category(app: AppHandle, filename: String, out_filename: String, overwrite: Option<bool>, dry_run: Option<bool>) -> String {
+
+    let report = match export_xlsx_category_sheets(app.clone(), filename, out_filename, overwrite.unwrap_or(false), dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 把 CSV 檔案改寫成固定格式 (依 Name 排序、固定欄位順序與分隔方式，重複執行結果相同)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (可以與來源相同)
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果的 JSON 字符串，失敗
+#[tauri::command]
+fn canonicalize_file(app: AppHandle, filename: String, out_filename: String, dry_run: Option<bool>) -> String {
+
+    let report = match canonicalize_csv(app.clone(), filename, out_filename, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 把多個多值欄位合併成一個新的欄位後輸出成新的 CSV 檔案 (來源欄位會被移除)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `source_columns_json`: 來源多值欄位的 JSON 陣列字串，例如: ["type", "category", "language"]
+/// - `target_column`: 新欄位的名稱，例如: "Tags"
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果與重疊記錄數的 JSON 字符串，失敗
+#[tauri::command]
+fn merge_columns(app: AppHandle, filename: String, source_columns_json: String, target_column: String, out_filename: String, dry_run: Option<bool>) -> String {
+
+    let report = match merge_csv_columns(app.clone(), filename, source_columns_json, target_column, out_filename, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 把 http:// 開頭的 URL 改成 https:// 後輸出成新的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果與改寫筆數的 JSON 字符串，失敗
+#[tauri::command]
+fn upgrade_https(app: AppHandle, filename: String, out_filename: String, dry_run: Option<bool>) -> String {
+
+    let report = match upgrade_https_csv(app.clone(), filename, out_filename, dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 正規化 URL 後輸出成新的 CSV 檔案 (主機名稱轉小寫、移除預設連接埠，讓以 URL 去重更可靠)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `strip_trailing_slash`: 移除路徑結尾的斜線 (可省略，預設保留)
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果、改寫筆數與無法解析 URL 的 JSON 字符串，失敗
+#[tauri::command]
+fn normalize_urls(app: AppHandle, filename: String, out_filename: String, strip_trailing_slash: Option<bool>, dry_run: Option<bool>) -> String {
+
+    let report = match normalize_csv_urls(app.clone(), filename, out_filename, strip_trailing_slash.unwrap_or(false), dry_run.unwrap_or(false)) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 移除完全沒有資料的欄位後輸出成新的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入 (可省略)
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回寫入結果的 JSON 字符串，失敗
+#[tauri::command]
+fn drop_empty_columns(app: AppHandle, filename: String, out_filename: String, dry_run: Option<bool>, options: Option<ReadOptions>) -> String {
+
+    let report = match drop_empty_csv_columns(app.clone(), filename, out_filename, dry_run.unwrap_or(false), &options.unwrap_or_default()) {
+        Ok(report) => report,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": report }).to_string()
+}
+
+/// 取得符合篩選條件的記錄中，某個欄位的不重複值 (例如: 選了 OS 之後只顯示該 OS 有的 Type)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 要取值的欄位名稱
+/// - `filters_json`: 篩選條件的 JSON 字串，例如: {"os": ["Linux"]}
+/// ## 返回
+/// - `String`: 成功時返回不重複值的 JSON 字符串，失敗
+#[tauri::command]
+fn distinct_where(app: AppHandle, filename: String, column: String, filters_json: String) -> String {
+
+    let values = match distinct_values_where(app.clone(), filename, column, filters_json) {
+        Ok(values) => values,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": values }).to_string()
+}
+
+/// 量測解析 CSV 檔案的速度 (筆數、位元組數、毫秒、每秒筆數、每秒 MB)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `columns`: 另外量測只解析這些欄位的耗時與加速倍數 (可省略)
+/// ## 返回
+/// - `String`: 成功時返回量測結果的 JSON 字符串，失敗
+#[tauri::command]
+fn benchmark_parse(app: AppHandle, filename: String, columns: Option<Vec<String>>) -> String {
+
+    let benchmark = match benchmark_csv_parse(app.clone(), filename, &columns.unwrap_or_default()) {
+        Ok(benchmark) => benchmark,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": benchmark }).to_string()
+}
+
+/// 檢查複合鍵是否唯一，返回鍵值相同的記錄群組與行號
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `key_columns_json`: 鍵欄位的 JSON 陣列字串，例如: ["name", "url"]
+/// ## 返回
+/// - `String`: 成功時返回重複群組的 JSON 字符串 (全部唯一時為空陣列)，失敗
+#[tauri::command]
+fn check_unique(app: AppHandle, filename: String, key_columns_json: String) -> String {
+
+    let groups = match duplicate_key_groups(app.clone(), filename, key_columns_json) {
+        Ok(groups) => groups,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": groups }).to_string()
+}
+
+/// 只讀取標題列與第一筆資料 (匯入預覽用，不限欄位格式)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回標題列與範例資料的 JSON 字符串，失敗
+#[tauri::command]
+fn peek_csv(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let peek = match peek_csv_file(app.clone(), filename, &options.unwrap_or_default()) {
+        Ok(peek) => peek,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": peek }).to_string()
+}
+
+/// 取得檔案大小、修改時間、標題列與資料列數 (不解析欄位內容，可在讀取大檔案前先顯示警告)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回檔案資訊的 JSON 字符串，失敗
+#[tauri::command]
+fn csv_metadata(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let metadata = match csv_file_metadata(app, filename, &options.unwrap_or_default()) {
+        Ok(metadata) => metadata,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": metadata }).to_string()
+}
+
+/// 找出 URL 不是 https 的記錄 (http、ftp 或沒有 scheme)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回不符合記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn check_https(app: AppHandle, filename: String) -> String {
+
+    let violations = match non_https_urls(app.clone(), filename) {
+        Ok(violations) => violations,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": violations }).to_string()
+}
+
+/// 找出 Notes (或所有文字欄位) 含有禁用詞的記錄 (不分大小寫，不會修改檔案)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `terms_json`: 禁用詞的 JSON 字串陣列，例如: ["spam", "free money"] (可省略)
+/// - `terms_filename`: config 資料夾內禁用詞檔案的名稱，與 terms_json 合併使用 (可省略)
+/// - `all_fields`: 是否檢查所有文字欄位 (可省略，預設只檢查 Notes)
+/// ## 返回
+/// - `String`: 成功時返回符合記錄 (行號、名稱、欄位、禁用詞) 的 JSON 字符串，失敗
+#[tauri::command]
+fn check_forbidden(app: AppHandle, filename: String, terms_json: Option<String>, terms_filename: Option<String>, all_fields: Option<bool>) -> String {
+
+    let mut terms: Vec<String> = match terms_json.as_deref().map(serde_json::from_str::<Vec<String>>).transpose() {
+        Ok(terms) => terms.unwrap_or_default(),
+        Err(error) => return serde_json::json!({ "error": format!("Invalid terms JSON: {}", error) }).to_string(),
+    };
+
+    if let Some(terms_filename) = terms_filename {
+        match read_forbidden_terms(&app, terms_filename) {
+            Ok(file_terms) => terms.extend(file_terms),
+            Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+        }
+    }
+
+    let matches = match forbidden_term_matches(app.clone(), filename, &terms, all_fields.unwrap_or_default()) {
+        Ok(matches) => matches,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": matches }).to_string()
+}
+
+/// 找出多值欄位的值數量超過上限的記錄 (例如每筆記錄最多 5 個 Type)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱 (Platform / Type / OS / Language / Category)
+/// - `max`: 值數量的上限
+/// ## 返回
+/// - `String`: 成功時返回超過上限記錄 (行號、名稱、數量) 的 JSON 字符串，失敗
+#[tauri::command]
+fn check_tag_limits(app: AppHandle, filename: String, column: String, max: usize) -> String {
+
+    let violations = match tag_limit_violations(app.clone(), filename, column, max) {
+        Ok(violations) => violations,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": violations }).to_string()
+}
+
+/// 找出名稱列表中，在檔案內沒有對應記錄的名稱 (不分大小寫)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `names_json`: 名稱的 JSON 陣列字串
+/// ## 返回
+/// - `String`: 成功時返回缺少名稱的 JSON 字符串 (依輸入順序)，失敗
+#[tauri::command]
+fn find_missing(app: AppHandle, filename: String, names_json: String) -> String {
+
+    let names = match missing_names(app.clone(), filename, names_json) {
+        Ok(names) => names,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": names }).to_string()
+}
+
+/// 取得檔案內容的一行摘要 (檔案列表的副標題用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回摘要的 JSON 字符串，失敗
+#[tauri::command]
+fn summarize_csv(app: AppHandle, filename: String) -> String {
+
+    let summary = match summarize_csv_file(app.clone(), filename) {
+        Ok(summary) => summary,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": summary }).to_string()
+}
+
+/// 讀取檔案的變更記錄 (寫入類指令成功時會自動記錄)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回變更記錄的 JSON 字符串，失敗
+#[tauri::command]
+fn read_change_log(app: AppHandle, filename: String) -> String {
+
+    let entries = match read_change_log_entries(app.clone(), filename) {
+        Ok(entries) => entries,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": entries }).to_string()
+}
+
+/// 找出標題列中重複的欄位名稱 (例如兩個 Type 欄位)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回重複欄位名稱的 JSON 字符串，失敗
+#[tauri::command]
+fn check_duplicate_headers(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let duplicates = match duplicate_header_names(app, filename, &options.unwrap_or_default()) {
+        Ok(duplicates) => duplicates,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": duplicates }).to_string()
+}
+
+/// 驗證單一 CSV 檔案 (重複的標題、分隔符號不一致、解析錯誤、非 https 的 URL、重複的 Name + URL)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回問題列表的 JSON 字符串 (沒有問題時為空陣列)，失敗
+#[tauri::command]
+fn validate_csv(app: AppHandle, filename: String) -> String {
+
+    let problems = match validate_csv_file(app, filename) {
+        Ok(problems) => problems,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": problems }).to_string()
+}
+
+/// 平行驗證 document 資料夾內所有的 .csv 檔案 (發佈前的 CI 檢查，單一檔案失敗不會中斷其他檔案)
+/// - 驗證在 blocking 執行緒上進行，不會佔用 async runtime 的 worker
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `concurrency`: 同時驗證的檔案數上限 (可省略，預設為 CPU 數)
+/// ## 返回
+/// - `String`: 成功時返回每個檔案驗證結果的 JSON 字符串，失敗
+#[tauri::command]
+async fn validate_all(app: AppHandle, concurrency: Option<usize>) -> String {
+
+    let concurrency = concurrency.unwrap_or_else(|| std::thread::available_parallelism().map(|count| count.get()).unwrap_or(1));
+    let reports = match tauri::async_runtime::spawn_blocking(move || validate_folder_files(app, concurrency)).await {
+        Ok(Ok(reports)) => reports,
+        Ok(Err(error)) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": reports }).to_string()
+}
+
+/// 找出完全沒有資料的欄位
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回空白欄位名稱的 JSON 字符串，失敗
+#[tauri::command]
+fn find_empty_columns(app: AppHandle, filename: String, options: Option<ReadOptions>) -> String {
+
+    let columns = match empty_csv_columns(app.clone(), filename, &options.unwrap_or_default()) {
+        Ok(columns) => columns,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": columns }).to_string()
+}
+
+/// 檢查資料列數是否在範圍內 (避免檔案被意外截斷或重複)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `min`: 最少列數 (可省略)
+/// - `max`: 最多列數 (可省略)
+/// - `options`: 讀取選項 (可省略，使用其中的 delimiter / encoding)
+/// ## 返回
+/// - `String`: 成功時返回檢查結果的 JSON 字符串，失敗
+#[tauri::command]
+fn check_row_count(app: AppHandle, filename: String, min: Option<u64>, max: Option<u64>, options: Option<ReadOptions>) -> String {
+
+    let check = match check_csv_row_count(app.clone(), filename, min, max, &options.unwrap_or_default()) {
+        Ok(check) => check,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": check }).to_string()
+}
+
+/// 計算記錄內容的指紋 (只與資料有關，空白、欄位順序、記錄順序不同的檔案會得到相同的指紋)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `String`: 成功時返回十六進位 SHA-256 的 JSON 字符串，失敗
+#[tauri::command]
+fn records_fingerprint(app: AppHandle, filename: String) -> String {
+
+    let fingerprint = match records_fingerprint_hash(app, filename) {
+        Ok(fingerprint) => fingerprint,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": fingerprint }).to_string()
+}
+
+/// 比較兩個資料夾內的檔案 (同步工具用: 只在 A、只在 B、內容不同)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `dir_a`: 資料夾 A
+/// - `dir_b`: 資料夾 B
+/// ## 返回
+/// - `String`: 成功時返回三個檔名列表的 JSON 字符串，失敗
+#[tauri::command]
+fn diff_folders(app: AppHandle, dir_a: String, dir_b: String) -> String {
+
+    let diff = match diff_folder_files(app.clone(), dir_a, dir_b) {
+        Ok(diff) => diff,
+        Err(error) => return serde_json::json!({ "error": error.to_string() }).to_string(),
+    };
+
+    serde_json::json!({ "result": diff }).to_string()
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .setup(|app| {
+            let mut warnings: Vec<String> = Vec::new();
+            app.manage(load_app_config(app.handle(), &mut warnings));
+
+            match logger_setting(app) {
+                Ok(()) => for warning in warnings.iter() { warn!("{}", warning); },
+                Err(error) => {
+                    eprintln!("Failed to setup logging: {}", error);
+                    for warning in warnings.iter() { eprintln!("{}", warning); }
+                }
+            }
+
+            match document_directory(app.handle()) {
+                Ok(folder) => if let Err(error) = app.state::<FolderWatcher>().start(app.handle().clone(), folder) { warn!("Failed to watch document folder: {}", error); },
+                Err(error) => warn!("Document folder not found, skip watching: {}", error),
+            }
+
             Ok(())
         })
+        .manage(CsvCache::default())
+        .manage(FolderWatcher::default())
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![read_csv, csv_list, read_type, read_json_file])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(tauri::generate_handler![read_csv, read_csv_stream, stream_csv, read_csv_merged, search_csv, sort_csv, csv_list, json_list, read_type, read_type_counts, read_facet_counts, read_json_file, set_log_level, detect_inconsistent_delimiters, check_example_prefix, new_csv_template, create_csv_template, tag_pairs, export_wide, export_subset, canonicalize_file, merge_columns, upgrade_https, drop_empty_columns, distinct_where, benchmark_parse, read_csv_with_rules, check_unique, peek_csv, csv_metadata, check_https, find_missing, summarize_csv, read_change_log, find_empty_columns, check_row_count, read_csv_geojson, diff_folders, clear_csv_cache, row_sizes, check_tag_limits, read_distinct, check_forbidden, export_xlsx_by_category, export_csv_to_json, normalize_urls, records_fingerprint, read_csv_profiled, check_duplicate_headers, read_csv_flat, validate_csv, validate_all, read_csv_dynamic, filter_csv, write_csv])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event { app.state::<FolderWatcher>().stop(); }
+        });
 }
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use crate::library::models::{CsvRecord, ParseNotes};
+
+/// 每個欄位 (小寫) 的不重複值
+pub type DistinctSets = HashMap<String, Vec<String>>;
+
+/// 一個檔案解析後的結果 (記錄、每筆記錄在檔案中的起始行號、附帶資訊與由記錄算出的不重複值)
+pub struct CacheEntry {
+    pub records: Vec<CsvRecord>,
+    pub lines: Vec<u64>,
+    pub notes: ParseNotes,
+    pub distinct: DistinctSets,
+}
+
+/// 單一檔案的快取槽 (修改時間與解析設定都相同時才算命中)
+struct CacheSlot {
+    modified: SystemTime,
+    signature: String,
+    entry: Arc<CacheEntry>,
+}
+
+/// CSV 解析結果的快取 (以 (檔案路徑, 修改時間, 解析設定) 為鍵，檔案一被修改就自動失效)
+/// - 每個檔案有自己的鎖: 同一個檔案同時被讀取時只會解析一次，不同檔案之間不會互相等待
+#[derive(Default)]
+pub struct CsvCache {
+    slots: Mutex<HashMap<PathBuf, Arc<Mutex<Option<CacheSlot>>>>>,
+}
+
+impl CsvCache {
+
+    /// 取得快取的解析結果，未命中 (或已過期) 時呼叫 `parse` 解析並更新快取
+    /// ## 參數
+    /// - `path`: CSV 檔案路徑
+    /// - `modified`: 檔案目前的修改時間
+    /// - `signature`: 會影響解析結果的設定 (例如: 分隔符號)
+    /// - `parse`: 解析檔案的函式
+    /// ## 返回
+    /// - `Result<Arc<CacheEntry>, E>`: 成功時返回解析結果，解析失敗時返回錯誤 (不會寫入快取)
+    pub fn get_or_parse<E>(&self, path: &Path, modified: SystemTime, signature: &str, parse: impl FnOnce() -> Result<CacheEntry, E>) -> Result<Arc<CacheEntry>, E> {
+
+        let slot = self.slot(path);
+        let mut slot = slot.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(cached) = slot.as_ref().filter(|cached| cached.modified == modified && cached.signature == signature) {
+            return Ok(cached.entry.clone());
+        }
+
+        let entry = Arc::new(parse()?);
+        *slot = Some(CacheSlot { modified, signature: signature.to_string(), entry: entry.clone() });
+
+        Ok(entry)
+    }
+
+    /// 清空所有快取
+    /// ## 返回
+    /// - `usize`: 被清除的檔案數
+    pub fn clear(&self) -> usize {
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        let count = slots.len();
+
+        slots.clear();
+        count
+    }
+
+    /// 取得 (或建立) 檔案的快取槽 (只短暫鎖住整個表，不會在解析時鎖住其他檔案)
+    /// ## 參數
+    /// - `path`: CSV 檔案路徑
+    /// ## 返回
+    /// - `Arc<Mutex<Option<CacheSlot>>>`: 檔案的快取槽
+    fn slot(&self, path: &Path) -> Arc<Mutex<Option<CacheSlot>>> {
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        slots.entry(path.to_path_buf()).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    fn entry(name: &str) -> CacheEntry {
+        let record = CsvRecord { name: name.to_string(), notes: String::new(), url: String::new(), level: 1, example: None, platform: vec![], r#type: vec![], os: vec![], language: vec![], category: vec![] };
+        CacheEntry { records: vec![record], lines: vec![2], notes: ParseNotes::default(), distinct: DistinctSets::new() }
+    }
+
+    #[test]
+    fn unchanged_file_reuses_the_cached_entry() {
+        let cache = CsvCache::default();
+        let path = Path::new("tools.csv");
+        let modified = SystemTime::UNIX_EPOCH;
+        let parses = Cell::new(0);
+        let parse = || { parses.set(parses.get() + 1); Ok::<_, ()>(entry("nmap")) };
+
+        let first = cache.get_or_parse(path, modified, ",", parse).unwrap();
+        let second = cache.get_or_parse(path, modified, ",", parse).unwrap();
+
+        assert_eq!(parses.get(), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn changed_mtime_or_signature_parses_again() {
+        let cache = CsvCache::default();
+        let path = Path::new("tools.csv");
+        let modified = SystemTime::UNIX_EPOCH;
+        let parses = Cell::new(0);
+        let parse = |name: &'static str| { let parses = &parses; move || { parses.set(parses.get() + 1); Ok::<_, ()>(entry(name)) } };
+
+        let original = cache.get_or_parse(path, modified, ",", parse("nmap")).unwrap();
+        let touched = cache.get_or_parse(path, modified + Duration::from_secs(1), ",", parse("curl")).unwrap();
+        let resigned = cache.get_or_parse(path, modified + Duration::from_secs(1), ";", parse("wget")).unwrap();
+
+        assert_eq!(parses.get(), 3);
+        assert_eq!(original.records[0].name, "nmap");
+        assert_eq!(touched.records[0].name, "curl");
+        assert_eq!(resigned.records[0].name, "wget");
+
+        let cached = cache.get_or_parse(path, modified + Duration::from_secs(1), ";", parse("ignored")).unwrap();
+        assert_eq!(parses.get(), 3);
+        assert!(Arc::ptr_eq(&resigned, &cached));
+    }
+
+    #[test]
+    fn failed_parse_is_not_cached() {
+        let cache = CsvCache::default();
+        let path = Path::new("tools.csv");
+
+        assert!(cache.get_or_parse(path, SystemTime::UNIX_EPOCH, ",", || Err::<CacheEntry, _>("broken")).is_err());
+
+        let parsed = cache.get_or_parse(path, SystemTime::UNIX_EPOCH, ",", || Ok::<_, &str>(entry("nmap"))).unwrap();
+        assert_eq!(parsed.records[0].name, "nmap");
+    }
+}
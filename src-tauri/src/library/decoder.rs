@@ -0,0 +1,93 @@
+use std::io::{Error, ErrorKind, Read};
+
+use encoding_rs::{Decoder, DecoderResult, Encoding};
+
+use crate::library::error::CsvError;
+
+/// 讀取與轉換時使用的緩衝區大小
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// 邊讀取邊把其他文字編碼轉成 UTF-8 的 Reader (開頭的 BOM 會被移除，記憶體用量不隨檔案大小成長)
+/// - 遇到無法解碼的位元組時返回 InvalidData，內含帶有行號的 CsvError::Parse (不會產生亂碼)
+pub struct DecodingReader<R> {
+    inner: R,
+    encoding: &'static Encoding,
+    decoder: Decoder,
+    input: Box<[u8]>,
+    input_start: usize,
+    input_end: usize,
+    output: Box<[u8]>,
+    output_start: usize,
+    output_end: usize,
+    eof: bool,
+    finished: bool,
+    line: u64,
+    malformed_line: Option<u64>,
+}
+
+impl<R: Read> DecodingReader<R> {
+
+    /// 建立轉換用的 Reader
+    /// ## 參數
+    /// - `inner`: 原始的位元組來源
+    /// - `encoding`: 原始內容的文字編碼
+    pub fn new(inner: R, encoding: &'static Encoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            decoder: encoding.new_decoder_with_bom_removal(),
+            input: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            input_start: 0,
+            input_end: 0,
+            output: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            output_start: 0,
+            output_end: 0,
+            eof: false,
+            finished: false,
+            line: 1,
+            malformed_line: None,
+        }
+    }
+}
+
+impl<R: Read> Read for DecodingReader<R> {
+
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+
+        loop {
+
+            if self.output_start < self.output_end {
+                let count = buffer.len().min(self.output_end - self.output_start);
+                buffer[..count].copy_from_slice(&self.output[self.output_start..self.output_start + count]);
+                self.output_start += count;
+                return Ok(count);
+            }
+
+            if let Some(line) = self.malformed_line {
+                let message = format!("File is not valid {} (check the encoding option)", self.encoding.name());
+                return Err(Error::new(ErrorKind::InvalidData, CsvError::Parse { line: Some(line), row: None, field: None, message }));
+            }
+
+            if self.finished || buffer.is_empty() { return Ok(0); }
+
+            if self.input_start == self.input_end && !self.eof {
+                self.input_start = 0;
+                self.input_end = self.inner.read(&mut self.input)?;
+                self.eof = self.input_end == 0;
+            }
+
+            let (result, read, written) = self.decoder.decode_to_utf8_without_replacement(&self.input[self.input_start..self.input_end], &mut self.output, self.eof);
+
+            self.input_start += read;
+            self.output_start = 0;
+            self.output_end = written;
+            self.line += self.output[..written].iter().filter(|byte| **byte == b'\n').count() as u64;
+
+            match result {
+                DecoderResult::InputEmpty => if self.eof { self.finished = true; },
+                DecoderResult::OutputFull => {},
+                DecoderResult::Malformed(..) => self.malformed_line = Some(self.line),
+            }
+        }
+    }
+}
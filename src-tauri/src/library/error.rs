@@ -0,0 +1,84 @@
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+use serde::Serialize;
+
+/// 讀取 CSV 時的錯誤 (序列化後帶有 kind 欄位，讓前端可以區分錯誤種類)
+/// - 例如: {"kind": "parse", "line": 12, "row": 11, "field": "Level", "message": "invalid digit found in string"}
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CsvError {
+
+    /// 檔名是空字串
+    EmptyFilename,
+
+    /// 參數不合法 (例如: 檔名包含路徑、分隔符號不是單一位元組)
+    InvalidInput { message: String },
+
+    /// 檔案或資料夾不存在
+    NotFound { message: String },
+
+    /// CSV 內容無法解析 (line 為檔案中的行號、row 為第幾筆資料 (從 1 開始)、field 為欄位名稱，無法得知時為 None)
+    #[serde(rename_all = "camelCase")]
+    Parse {
+        line: Option<u64>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        row: Option<u64>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        message: String,
+    },
+
+    /// 其他讀寫錯誤
+    Io { message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::EmptyFilename => write!(formatter, "Filename cannot be empty"),
+            CsvError::InvalidInput { message } => write!(formatter, "{}", message),
+            CsvError::NotFound { message } => write!(formatter, "{}", message),
+            CsvError::Parse { line, row, field, message } => {
+                if let Some(row) = row { write!(formatter, "row {}: ", row)?; }
+                if let (None, Some(line)) = (row, line) { write!(formatter, "line {}: ", line)?; }
+                if let Some(field) = field { write!(formatter, "field '{}': ", field)?; }
+                write!(formatter, "{}", message)
+            }
+            CsvError::Io { message } => write!(formatter, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<Error> for CsvError {
+    fn from(error: Error) -> Self {
+        let message = error.to_string();
+        let kind = error.kind();
+
+        if let Some(Ok(error)) = error.into_inner().map(|inner| inner.downcast::<CsvError>()) { return *error; }
+
+        match kind {
+            ErrorKind::InvalidInput => CsvError::InvalidInput { message },
+            ErrorKind::NotFound => CsvError::NotFound { message },
+            ErrorKind::InvalidData => CsvError::Parse { line: None, row: None, field: None, message },
+            _ => CsvError::Io { message },
+        }
+    }
+}
+
+impl From<CsvError> for Error {
+    fn from(error: CsvError) -> Self {
+        let kind = match error {
+            CsvError::EmptyFilename | CsvError::InvalidInput { .. } => ErrorKind::InvalidInput,
+            CsvError::NotFound { .. } => ErrorKind::NotFound,
+            CsvError::Parse { .. } => ErrorKind::InvalidData,
+            CsvError::Io { .. } => ErrorKind::Other,
+        };
+
+        Error::new(kind, error)
+    }
+}
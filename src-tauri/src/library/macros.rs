@@ -1,10 +1,28 @@
+use std::io::IsTerminal;
+
+/// 印出帶有 [檔案 - 行號] 的訊息，並同時以 log::debug! 寫入日誌檔
+/// - stdout 不是終端機 (例如: 導向檔案或 CI) 或設定了 NO_COLOR 時輸出純文字
 #[macro_export]
 macro_rules! ww_print {
-    ($message:expr) => {
-        println!("\n[{file} - {line}]\n{message}",
-            file = file!().green().bold(),
-            line = format!("line.{}", line!().to_string()).yellow().bold(),
-            message = $message
-        )
-    };
-}
\ No newline at end of file
+    ($message:expr) => {{
+        let message = $message;
+        log::debug!("[{} - line.{}] {}", file!(), line!(), message);
+
+        if $crate::library::macros::use_color() {
+            println!("\n[{file} - {line}]\n{message}",
+                file = file!().green().bold(),
+                line = format!("line.{}", line!().to_string()).yellow().bold(),
+                message = message
+            )
+        } else {
+            println!("\n[{} - line.{}]\n{}", file!(), line!(), message)
+        }
+    }};
+}
+
+/// 是否以 ANSI 色碼輸出 (NO_COLOR 有值時，或 stdout 不是終端機時不使用)
+/// ## 返回
+/// - `bool`: 是否使用顏色
+pub fn use_color() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && std::io::stdout().is_terminal()
+}
@@ -1,3 +1,7 @@
 pub mod models;
 pub mod utils;
-pub mod macros;
\ No newline at end of file
+pub mod macros;
+pub mod cache;
+pub mod error;
+pub mod watcher;
+pub mod decoder;
\ No newline at end of file
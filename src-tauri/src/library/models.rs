@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Serialize, Deserialize};
-use serde::de::Deserializer;
+use serde::de::{self, Deserializer, Visitor};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "PascalCase")]
 pub struct CsvRecord {
     pub name: String,
@@ -41,13 +43,753 @@ pub struct CsvRecord {
     pub category: Vec<String>,
 }
 
-/// 把字串轉換成平台列表
-/// - 例如: "Windows, Linux, macOS" 會轉換成 ["Windows", "Linux", "macOS"]
+impl CsvRecord {
+
+    /// 多值欄位的名稱 (以逗號分隔的欄位)
+    pub const MULTI_VALUE_COLUMNS: [&str; 5] = ["Platform", "Type", "OS", "Language", "Category"];
+
+    /// 依欄位名稱取得多值欄位的內容 (不分大小寫)
+    /// - 例如: "type" / "Type" => record.r#type
+    pub fn multi_values(&self, column: &str) -> Option<&Vec<String>> {
+        match column.to_lowercase().as_str() {
+            "platform" => Some(&self.platform),
+            "type" => Some(&self.r#type),
+            "os" => Some(&self.os),
+            "language" => Some(&self.language),
+            "category" => Some(&self.category),
+            _ => None,
+        }
+    }
+
+    /// 依欄位名稱取得 CSV 儲存格的文字 (不分大小寫，多值欄位以 join_multi_value 合併)
+    /// - 例如: "Level" => "3"、"Platform" => "Windows,Linux"
+    pub fn cell(&self, column: &str) -> Option<String> {
+        match column.to_lowercase().as_str() {
+            "name" => Some(self.name.clone()),
+            "notes" => Some(self.notes.clone()),
+            "url" => Some(self.url.clone()),
+            "level" => Some(self.level.to_string()),
+            "example" => Some(self.example.clone().unwrap_or_default()),
+            _ => self.multi_values(column).map(|values| join_multi_value(values)),
+        }
+    }
+
+    /// 依欄位名稱取得欄位內的值列表 (多值欄位返回全部的值，單值欄位返回非空白的那一個值)
+    /// - 例如: "Type" => ["CLI", "GPT"]、"Level" => ["3"]、未知欄位 => None
+    pub fn column_values(&self, column: &str) -> Option<Vec<String>> {
+
+        if let Some(values) = self.multi_values(column) {
+            return Some(values.iter().filter(|value| !value.is_empty()).cloned().collect());
+        }
+
+        self.cell(column).map(|value| if value.is_empty() { vec![] } else { vec![value] })
+    }
+
+    /// 文字欄位 (Name、Notes、URL、Example 與以逗號合併的多值欄位) 的總字元數 (Unicode 純量值，非位元組)
+    /// - 用於前端預估列表項目的顯示大小
+    pub fn text_length(&self) -> usize {
+        let texts = [self.name.as_str(), self.notes.as_str(), self.url.as_str(), self.example.as_deref().unwrap_or_default()];
+        let tags = Self::MULTI_VALUE_COLUMNS.iter().filter_map(|column| self.cell(column));
+
+        texts.iter().map(|text| text.chars().count()).sum::<usize>() + tags.map(|text| text.chars().count()).sum::<usize>()
+    }
+
+    /// 取得標題列的欄位名稱 (由 serde 屬性推導，與實際讀寫的欄位名稱一致)
+    /// - 例如: ["Name", "Notes", "URL", "Level", ...]
+    pub fn headers() -> Vec<String> {
+        serde_field_names::<CsvRecord>().iter().map(|name| name.to_string()).collect()
+    }
+}
+
+/// 同一個多值欄位內兩個值一起出現的次數
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagPair {
+    pub first: String,
+    pub second: String,
+    pub count: usize,
+}
+
+/// 寫入檔案的結果 (dry_run 時只回報預計的變更，檔案不會被修改)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteReport {
+    pub dry_run: bool,
+    pub path: String,
+    pub rows: usize,
+    pub columns: Vec<String>,
+}
+
+/// 欄位篩選條件: 欄位名稱 => 篩選條件 (不分大小寫，欄位之間為 AND、值之間為 OR，空陣列代表不限制)
+/// - 例如: {"os": ["Linux"], "type": {"include": ["CLI", "GUI"], "exclude": ["Deprecated"]}}
+pub type FacetFilters = BTreeMap<String, FacetFilter>;
+
+/// 單一欄位的篩選條件
+/// - 陣列: 包含其中任一個值，例如: ["CLI", "GUI"]
+/// - 物件: 可同時指定包含與排除 (有任一個排除的值就不符合)，例如: {"exclude": ["Deprecated"]}
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum FacetFilter {
+    Include(Vec<String>),
+    Rule(FacetRule),
+}
+
+/// 物件形式的篩選條件
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FacetRule {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl FacetFilter {
+
+    /// 要包含的值 (空陣列代表不限制)
+    pub fn include(&self) -> &[String] {
+        match self {
+            FacetFilter::Include(values) => values,
+            FacetFilter::Rule(rule) => &rule.include,
+        }
+    }
+
+    /// 要排除的值
+    pub fn exclude(&self) -> &[String] {
+        match self {
+            FacetFilter::Include(_) => &[],
+            FacetFilter::Rule(rule) => &rule.exclude,
+        }
+    }
+}
+
+/// export_subset 的選項 (同時指定要保留的欄位與資料列)
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SubsetOptions {
+
+    /// 要保留的欄位 (依此順序輸出，空陣列代表全部欄位)，例如: ["Name", "URL"]
+    pub columns: Vec<String>,
+
+    /// 資料列的篩選條件
+    pub filters: FacetFilters,
+}
+
+/// read_csv_stream 透過 Channel 傳給前端的訊息
+/// - 例如: {"event": "records", "data": {"records": [...]}}、{"event": "done", "data": {"total": 142}}
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+pub enum CsvStreamMessage {
+    Records { records: Vec<CsvRecord> },
+    Done { total: usize },
+}
+
+/// 解析速度的量測結果
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseBenchmark {
+    pub rows: usize,
+    pub bytes: u64,
+    pub elapsed_ms: f64,
+    pub rows_per_sec: f64,
+    pub mb_per_sec: f64,
+
+    /// 只解析指定欄位 (read_csv 的 columns 選項) 的毫秒數
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns_elapsed_ms: Option<f64>,
+
+    /// 完整解析 / 只解析指定欄位的時間比
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speedup: Option<f64>,
+}
+
+/// 資源目錄根部的 app.json (資源資料夾的名稱，檔案不存在或欄位省略時使用預設值)
+/// - 例如: {"documentDir": "data", "configDir": "settings", "logsDir": "logs"}
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct AppConfig {
+
+    /// CSV 檔案的資料夾，預設為 "document"
+    pub document_dir: String,
+
+    /// JSON 設定檔的資料夾，預設為 "config"
+    pub config_dir: String,
+
+    /// 日誌檔的資料夾，預設為 "logs"
+    pub logs_dir: String,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            document_dir: String::from("document"),
+            config_dir: String::from("config"),
+            logs_dir: String::from("logs"),
+        }
+    }
+}
+
+/// config 資料夾內的 logging.json (日誌設定)
+/// - 例如: {"level": "info", "retentionDays": 14}
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct LoggingConfig {
+
+    /// 日誌等級 (off / error / warn / info / debug / trace)，省略時為 debug
+    pub level: Option<String>,
+
+    /// 保留最近幾天的日誌檔 (依檔名的日期判斷)，省略時為 30，0 代表不刪除
+    pub retention_days: Option<u32>,
+}
+
+/// 從 config 資料夾讀取的資料列規則 (所有條件都要符合)
+/// - 例如: {"minLevel": 2, "filters": {"platform": ["Linux"]}}
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct RecordRules {
+
+    /// Level 的最小值 (含)
+    pub min_level: Option<u8>,
+
+    /// Level 的最大值 (含)
+    pub max_level: Option<u8>,
+
+    /// 欄位篩選條件
+    pub filters: FacetFilters,
+}
+
+/// 複合鍵重複的記錄群組
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateKeyGroup {
+    pub key: Vec<String>,
+    pub lines: Vec<u64>,
+}
+
+/// 單一檔案的驗證結果 (沒有問題時 problems 為空陣列)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FileValidation {
+    pub file: String,
+    pub problems: Vec<String>,
+
+    /// 無法完成驗證的原因 (例如: 讀取失敗、驗證時 panic)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 合併多值欄位的結果
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+
+    #[serde(flatten)]
+    pub write: WriteReport,
+
+    /// 來源欄位之間有相同值的記錄數
+    pub overlapping: usize,
+}
+
+/// 標題列與第一筆資料 (原始字串，不對應到 CsvRecord)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvPeek {
+    pub headers: Vec<String>,
+    pub sample: Vec<String>,
+}
+
+/// 依 Category 分頁輸出 xlsx 的結果 (`rows` 為所有工作表的資料列總數)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct XlsxReport {
+
+    #[serde(flatten)]
+    pub write: WriteReport,
+
+    pub sheets: Vec<XlsxSheet>,
+}
+
+/// xlsx 中的一個工作表
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct XlsxSheet {
+
+    /// 工作表名稱 (已處理 Excel 的長度與字元限制)
+    pub name: String,
+
+    /// 原始的 Category 值
+    pub category: String,
+    pub rows: usize,
+}
+
+/// URL 不是 https 的記錄
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlViolation {
+    pub line: u64,
+    pub name: String,
+    pub url: String,
+    pub scheme: String,
+}
+
+/// 文字欄位含有禁用詞的記錄 (同一欄位符合多個禁用詞時，每個禁用詞各一筆)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ForbiddenMatch {
+    pub line: u64,
+    pub name: String,
+    pub field: String,
+    pub term: String,
+}
+
+/// 分頁後的不重複值 (`total` 為符合前綴的不重複值總數)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DistinctPage {
+    pub values: Vec<String>,
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// 多值欄位的值數量超過上限的記錄
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TagLimitViolation {
+    pub line: u64,
+    pub name: String,
+    pub count: usize,
+    pub max: usize,
+}
+
+/// 改寫欄位值後輸出的結果
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeReport {
+
+    #[serde(flatten)]
+    pub write: WriteReport,
+
+    /// 有被改寫的記錄數
+    pub changed: usize,
+}
+
+/// 正規化 URL 後輸出的結果
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlNormalizeReport {
+
+    #[serde(flatten)]
+    pub write: WriteReport,
+
+    /// 有被改寫的 URL 數
+    pub changed: usize,
+
+    /// 無法解析而保留原樣的 URL
+    pub unparseable: Vec<InvalidUrl>,
+}
+
+/// 無法解析的 URL
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidUrl {
+    pub line: u64,
+    pub name: String,
+    pub url: String,
+    pub message: String,
+}
+
+/// 不解析欄位內容就能取得的檔案資訊 (modified 為本地時間 "YYYY-MM-DD HH:MM:SS"，rows 不含標題列)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvMetadata {
+    pub size: u64,
+    pub modified: String,
+    pub headers: Vec<String>,
+    pub rows: u64,
+}
+
+/// 檔案內容的摘要 (text 為顯示用的單行文字，其餘欄位給前端自行組合)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvSummary {
+    pub records: usize,
+    pub types: usize,
+    pub platforms: usize,
+    pub min_level: Option<u8>,
+    pub max_level: Option<u8>,
+    pub text: String,
+}
+
+/// 單一欄位的統計資料 (read_csv 的 profile 選項)
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnProfile {
+
+    /// 不重複值的數量 (多值欄位以個別的值計算)
+    pub distinct: usize,
+
+    /// 有值的記錄數
+    pub filled: usize,
+
+    /// 有值的記錄比例 (0.0 ~ 1.0，沒有記錄時為 0.0)
+    pub completeness: f64,
+
+    /// 所有值都是數字時的最小值 / 最大值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+/// 檔案的變更記錄 (一次成功的寫入為一筆)
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogEntry {
+    pub timestamp: String,
+    pub operation: String,
+    pub rows: usize,
+}
+
+/// 資料列數的檢查結果
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RowCountCheck {
+    pub passed: bool,
+    pub count: u64,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub message: String,
+}
+
+/// 被略過的資料列 (行號、第幾筆資料 (從 1 開始，可得知時) 與原因)
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedRow {
+    pub line: u64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<u64>,
+    pub message: String,
+}
+
+/// 兩個資料夾的差異 (檔名)
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderDiff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub different: Vec<String>,
+}
+
+/// read_csv 的讀取選項 (前端未傳入的欄位使用預設值)
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReadOptions {
+
+    /// 欄位分隔符號 (單一位元組)，例如: "\t"、";"，省略時為逗號
+    pub delimiter: Option<String>,
+
+    /// 檔案的文字編碼 (WHATWG 標籤)，例如: "big5"、"latin1"，省略時為 UTF-8；開頭的 BOM 會被移除
+    pub encoding: Option<String>,
+
+    /// 分頁: 略過前幾筆記錄 (在排序、反轉之後)，超過總筆數時返回空陣列
+    pub offset: usize,
+
+    /// 分頁: 最多返回幾筆記錄，0 代表不限制
+    pub limit: usize,
+
+    /// 依檔案出現順序反轉記錄 (先過濾、再反轉)
+    pub reverse: bool,
+
+    /// 依 Name 排序 (在反轉之前)
+    pub sort_by_name: bool,
+
+    /// 排序 Name 時使用的語系 (ICU collation)，例如: "zh-TW"，省略時不分大小寫逐字比較
+    pub locale: Option<String>,
+
+    /// 嚴格檢查欄位數 (預設容許每一行多出一個空白的結尾欄位)
+    pub strict_trailing_field: bool,
+
+    /// 輸出成 JSON 布林值的欄位名稱 (不分大小寫)，例如: ["Active"]
+    pub boolean_columns: Vec<String>,
+
+    /// 代表 true 的字串 (不分大小寫)，空陣列時使用 yes / true / 1
+    pub truthy_tokens: Vec<String>,
+
+    /// 代表 false 的字串 (不分大小寫)，空陣列時使用 no / false / 0
+    pub falsy_tokens: Vec<String>,
+
+    /// Level 為小數時的捨入方式 (預設不轉換，小數會解析失敗)
+    pub level_rounding: Option<LevelRounding>,
+
+    /// 多值欄位輸出成以此分隔符號合併的字串 (預設輸出成陣列)，例如: ", "
+    pub join_multivalue: Option<String>,
+
+    /// 把數值欄位中「(5)」這類會計格式的數字解析成負數「-5」(可含千分位，例如: "(1,234.50)" => "-1234.50")
+    pub parenthesized_negatives: bool,
+
+    /// 套用 parenthesizedNegatives 的數值欄位名稱 (不分大小寫)，空陣列時為 ["Level"]
+    pub numeric_columns: Vec<String>,
+
+    /// 多值欄位改用的分隔符號，例如: "|" (設定時只以它拆開，逗號都當成文字)，省略時以逗號拆開並支援「\,」跳脫
+    pub multi_value_separator: Option<String>,
+
+    /// 只解析並輸出這些欄位 (不分大小寫)，例如: ["Name", "Level"]，空陣列時解析完整的 CsvRecord
+    /// - 以 StringRecord 索引直接讀取，略過其他欄位的解析；只套用 reverse / sortByName / locale / joinMultivalue / notesDelimiter
+    pub columns: Vec<String>,
+
+    /// 把 Notes 以此分隔符號拆成陣列輸出 (預設輸出原本的字串)，例如: "|"
+    pub notes_delimiter: Option<String>,
+
+    /// 搭配 notesDelimiter，依位置把拆開的 Notes 輸出成物件，例如: ["summary", "details", "caveats"]
+    /// - 段數不足時補空字串，段數較多時多出來的部分以分隔符號併入最後一個名稱
+    pub notes_labels: Vec<String>,
+
+    /// 另外輸出每個欄位的統計資料 (不重複值數量、完整度、數字欄位的最小值 / 最大值)
+    pub profile: bool,
+
+    /// 標題列有重複欄位名稱時的處理方式 (預設返回錯誤)
+    pub duplicate_headers: DuplicateHeaders,
+
+    /// 檢查每筆記錄的 URL 是否能解析 (空白的 URL 不檢查)，無法解析的記錄放在 warnings，不會中止讀取
+    pub validate: bool,
+
+    /// 略過無法解析的資料列 (欄位數不符、值的格式錯誤) 並放在 skipped，而不是返回錯誤 (預設為嚴格模式)
+    pub lenient: bool,
+}
+
+/// 標題列有重複欄位名稱時的處理方式
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateHeaders {
+
+    /// 返回錯誤 (列出重複的欄位名稱)
+    #[default]
+    Error,
+
+    /// 第二個之後的欄位加上編號，例如: Type、Type_2 (只有第一個會對應到 CsvRecord)
+    Rename,
+}
+
+/// 小數轉整數的捨入方式
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LevelRounding {
+    Floor,
+    Ceil,
+    Nearest,
+}
+
+/// 解析過程中的附帶資訊 (沒有內容的欄位不會輸出)
+#[derive(Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseNotes {
+
+    /// Level 由小數轉換成整數的記錄
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub coerced: Vec<CoercedLevel>,
+
+    /// URL 無法解析的記錄 (只在設定 validate 時檢查)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<UrlWarning>,
+
+    /// 無法解析而被略過的資料列 (只在設定 lenient 時略過，否則直接返回錯誤)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedRow>,
+}
+
+/// URL 無法解析的記錄 (line 為記錄在檔案中的起始行號，標題列為第 1 行)
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlWarning {
+    pub line: u64,
+    pub name: String,
+    pub url: String,
+    pub message: String,
+}
+
+/// Level 由小數轉換成整數的記錄
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoercedLevel {
+    pub line: u64,
+    pub original: String,
+    pub level: u8,
+}
+
+impl ReadOptions {
+
+    /// 預設代表 true 的字串
+    pub const DEFAULT_TRUTHY_TOKENS: [&str; 3] = ["yes", "true", "1"];
+
+    /// 預設代表 false 的字串
+    pub const DEFAULT_FALSY_TOKENS: [&str; 3] = ["no", "false", "0"];
+
+    /// 預設套用 parenthesizedNegatives 的數值欄位
+    pub const DEFAULT_NUMERIC_COLUMNS: [&str; 1] = ["Level"];
+
+    /// 把字串轉成布林值 (不分大小寫)
+    /// - 例如: "Yes" => Some(true)、"0" => Some(false)、"maybe" => None
+    pub fn boolean_token(&self, token: &str) -> Option<bool> {
+
+        let token = token.trim();
+        let matches = |tokens: &[String], defaults: &[&str]| {
+            if tokens.is_empty() { return defaults.iter().any(|value| value.eq_ignore_ascii_case(token)); }
+            tokens.iter().any(|value| value.trim().to_lowercase() == token.to_lowercase())
+        };
+
+        if matches(&self.truthy_tokens, &Self::DEFAULT_TRUTHY_TOKENS[..]) { return Some(true); }
+        if matches(&self.falsy_tokens, &Self::DEFAULT_FALSY_TOKENS[..]) { return Some(false); }
+        None
+    }
+
+    /// 依 multiValueSeparator 拆開多值欄位的文字 (空白的值會被略過)
+    /// - 例如: 分隔符號為 "|" 時 "Dev, Tools | CLI" => ["Dev, Tools", "CLI"]，沒有設定時規則同 split_multi_value
+    pub fn multi_values(&self, text: &str) -> Vec<String> {
+        match self.multi_value_separator.as_deref().filter(|separator| !separator.is_empty()) {
+            Some(separator) => text.split(separator).map(str::trim).filter(|value| !value.is_empty()).map(str::to_string).collect(),
+            None => split_multi_value(text),
+        }
+    }
+}
+
+/// Example 前綴檢查不符合的記錄
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExamplePrefixViolation {
+    pub line: u64,
+    pub name: String,
+    pub example: String,
+    pub expected: Vec<String>,
+}
+
+/// 多值欄位的預設分隔符號
+const MULTI_VALUE_SEPARATOR: char = ',';
+
+/// 把字串轉換成平台列表 (規則見 split_multi_value)
+/// - 例如: "Windows, Linux, macOS" 會轉換成 ["Windows", "Linux", "macOS"]，空白的儲存格會轉換成 []
 /// # 參數
 /// - `deserializer`: 用於反序列化的 Deserializer
 /// # 返回
 /// - `Result<Vec<String>, D::Error>`: 成功時返回平台列表，失敗時返回錯誤
 fn deserialize_platform<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error> where D: Deserializer<'de> {
     let str = String::deserialize(deserializer)?;
-    Ok(str.split(',').map(|str| str.trim().to_string()).collect())
+    Ok(split_multi_value(&str))
+}
+
+/// 把多值欄位的文字以逗號拆開 (與 CsvRecord 反序列化的規則相同，空白的值會被略過)
+/// - 「\,」代表文字的逗號，例如: "Windows, Linux" => ["Windows", "Linux"]、"Dev\, Tools, CLI" => ["Dev, Tools", "CLI"]
+/// - 儲存格外層的引號只屬於 CSV 格式，例如: 儲存格 `"Dev, Tools"` 仍會拆成 ["Dev", "Tools"]
+/// - 例如: "Windows,,Linux," => ["Windows", "Linux"]、"" => []
+/// - 要改用其他分隔符號 (例如: 「|」) 時設定 ReadOptions 的 multiValueSeparator
+pub fn split_multi_value(text: &str) -> Vec<String> {
+
+    let mut values = vec![];
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' if chars.peek() == Some(&MULTI_VALUE_SEPARATOR) => { current.push(MULTI_VALUE_SEPARATOR); chars.next(); },
+            MULTI_VALUE_SEPARATOR => values.push(std::mem::take(&mut current)),
+            _ => current.push(char),
+        }
+    }
+
+    values.push(current);
+    values.iter().map(|value| value.trim()).filter(|value| !value.is_empty()).map(str::to_string).collect()
+}
+
+/// 把多值欄位的值合併成儲存格的文字 (split_multi_value 的反向操作)
+/// - 值本身的逗號會寫成「\,」，讀回來時才不會被拆開，例如: ["Dev, Tools", "CLI"] => "Dev\, Tools,CLI"
+pub fn join_multi_value<S: AsRef<str>>(values: &[S]) -> String {
+    values.iter().map(|value| value.as_ref().replace(MULTI_VALUE_SEPARATOR, "\\,")).collect::<Vec<String>>().join(&MULTI_VALUE_SEPARATOR.to_string())
+}
+
+/// 取得結構在 serde 中的欄位名稱 (套用 rename / rename_all 之後的名稱，依宣告順序)
+/// # 返回
+/// - `&'static [&'static str]`: 欄位名稱列表，非結構時返回空陣列
+fn serde_field_names<'de, T>() -> &'static [&'static str] where T: Deserialize<'de> {
+
+    struct FieldNamesDeserializer<'a> {
+        fields: &'a mut Option<&'static [&'static str]>,
+    }
+
+    impl<'de> Deserializer<'de> for FieldNamesDeserializer<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V>(self, _name: &'static str, fields: &'static [&'static str], _visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+            *self.fields = Some(fields);
+            Err(de::Error::custom("field names collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields = None;
+    let _ = T::deserialize(FieldNamesDeserializer { fields: &mut fields });
+    fields.unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boolean_token_accepts_default_tokens() {
+        let options = ReadOptions::default();
+
+        for (token, expected) in [("yes", true), ("No", false), ("TRUE", true), ("false", false), ("1", true), ("0", false), (" yes ", true)] {
+            assert_eq!(options.boolean_token(token), Some(expected), "token {:?}", token);
+        }
+        assert_eq!(options.boolean_token("maybe"), None);
+    }
+
+    #[test]
+    fn boolean_token_uses_custom_tokens() {
+        let options = ReadOptions {
+            truthy_tokens: vec![String::from("On"), String::from("Y")],
+            falsy_tokens: vec![String::from("off")],
+            ..ReadOptions::default()
+        };
+
+        assert_eq!(options.boolean_token("on"), Some(true));
+        assert_eq!(options.boolean_token("y"), Some(true));
+        assert_eq!(options.boolean_token("OFF"), Some(false));
+        assert_eq!(options.boolean_token("yes"), None);
+    }
+
+    #[test]
+    fn split_multi_value_drops_empty_values() {
+        assert!(split_multi_value("").is_empty());
+        assert!(split_multi_value("  ").is_empty());
+        assert_eq!(split_multi_value("Windows"), ["Windows"]);
+        assert_eq!(split_multi_value("Windows, Linux,"), ["Windows", "Linux"]);
+        assert_eq!(split_multi_value("Windows,,Linux"), ["Windows", "Linux"]);
+    }
+
+    #[test]
+    fn join_multi_value_escapes_commas_for_split() {
+        let values = ["Dev, Tools", "CLI"];
+        let joined = join_multi_value(&values);
+
+        assert_eq!(joined, "Dev\\, Tools,CLI");
+        assert_eq!(split_multi_value(&joined), values);
+        assert_eq!(split_multi_value("A|B"), ["A|B"]);
+    }
+
+    #[test]
+    fn multi_values_uses_the_configured_separator() {
+        let options = ReadOptions { multi_value_separator: Some(String::from("|")), ..ReadOptions::default() };
+
+        assert_eq!(options.multi_values("Dev, Tools | CLI |"), ["Dev, Tools", "CLI"]);
+        assert_eq!(ReadOptions::default().multi_values("Dev\\, Tools, CLI"), ["Dev, Tools", "CLI"]);
+    }
 }
@@ -1,70 +1,495 @@
 use std::fmt::Debug;
-use std::fs::{read_dir, File, OpenOptions, create_dir_all};
-use std::io::{Error, ErrorKind, Write};
-use std::path::PathBuf;
-use std::collections::HashSet;
+use std::fs::{self, read_dir, read_to_string, File, OpenOptions, create_dir_all};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::time::{Instant, SystemTime};
+use std::str::FromStr;
+use std::cmp::Ordering;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
-use csv::Reader;
+use csv::{ByteRecord, QuoteStyle, Reader, ReaderBuilder, StringRecord, Terminator, Writer, WriterBuilder};
 use serde::de::{DeserializeOwned};
+use serde_json::{Map, Value};
 use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager};
-use env_logger::Env;
 use env_logger::{fmt::Color, Builder};
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDate};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use icu_collator::{Collator, CollatorOptions};
+use sha2::{Digest, Sha256};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use url::Url;
+use encoding_rs::{Encoding, UTF_8};
+use icu_locid::Locale;
+use icu_provider::DataLocale;
+use log::{info, warn, LevelFilter};
 use colored::Colorize;
 
-use crate::library::models::CsvRecord;
+use crate::library::models::{join_multi_value, CsvRecord, ReadOptions, FacetFilters, FacetFilter, FacetRule, SubsetOptions, ExamplePrefixViolation, TagPair, WriteReport, ParseBenchmark, RecordRules, DuplicateKeyGroup, FileValidation, ParseNotes, CoercedLevel, LevelRounding, DuplicateHeaders, MergeReport, CsvPeek, UrlViolation, TagLimitViolation, DistinctPage, ForbiddenMatch, XlsxReport, XlsxSheet, UrlNormalizeReport, InvalidUrl, ChangeReport, CsvSummary, ColumnProfile, ChangeLogEntry, RowCountCheck, SkippedRow, FolderDiff, LoggingConfig, CsvMetadata, AppConfig, UrlWarning};
+use crate::library::cache::{CacheEntry, CsvCache, DistinctSets};
+use crate::library::decoder::DecodingReader;
+use crate::library::error::CsvError;
 use crate::ww_print;
 
+/// xlsx 工作表名稱的最大長度
+const SHEET_NAME_MAX_LENGTH: usize = 31;
+
+/// 沒有 Category 的記錄所使用的工作表名稱
+const UNCATEGORIZED_SHEET: &str = "Uncategorized";
+
+/// 偵測混用分隔符號時的候選字元
+const DELIMITER_CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+
+/// 偵測混用分隔符號時最多取樣的行數
+const DELIMITER_SAMPLE_ROWS: usize = 1000;
+
+/// 日誌檔名的日期格式 (例如: 20250101.log)
+const LOG_FILE_DATE_FORMAT: &str = "%Y%m%d";
+
+/// 預設保留最近幾天的日誌檔
+const DEFAULT_LOG_RETENTION_DAYS: u32 = 30;
+
+/// UTF-8 的 BOM (Excel 匯出的 CSV 檔案開頭常帶有)
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 /// 從 CSV 檔案讀取記錄
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
 /// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, CsvError>`: 成功時返回記錄的向量，失敗時返回錯誤 (可區分檔名錯誤、檔案不存在、解析錯誤)
+pub fn read_csv_file(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<CsvRecord>, CsvError> {
+    let (records, _) = read_csv_file_with_notes(app, filename, options)?;
+    Ok(records)
+}
+
+/// 從 CSV 檔案讀取記錄，並返回解析過程中的附帶資訊 (例如: 被轉換的 Level、設定 validate 時無法解析的 URL)
+/// - 檔案的修改時間與解析設定沒有改變時直接使用快取，不會重新解析
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<(Vec<CsvRecord>, ParseNotes), CsvError>`: 成功時返回 (記錄, 附帶資訊)，失敗時返回錯誤
+pub fn read_csv_file_with_notes(app: AppHandle, filename: String, options: &ReadOptions) -> Result<(Vec<CsvRecord>, ParseNotes), CsvError> {
+    if filename.is_empty() { return Err(CsvError::EmptyFilename); }
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let entry = _cached_parse(&app, &resource_path, options)?;
+    let mut notes = entry.notes.clone();
+
+    if options.validate { notes.warnings = _url_warnings(&entry.records, &entry.lines); }
+
+    Ok((entry.records.clone(), notes))
+}
+
+/// 取得某欄位的不重複值 (優先使用快取；未命中時解析檔案並把所有欄位的不重複值存入快取)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 欄位名稱 (不分大小寫)
+/// - `options`: 讀取選項 (未命中快取時用來解析檔案)
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 成功時返回排序後的不重複值，失敗時返回錯誤
+pub fn cached_distinct_values(app: AppHandle, filename: String, column: &str, options: &ReadOptions) -> Result<Vec<String>, Error> {
+
+    _check_column(column)?;
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let entry = _cached_parse(&app, &resource_path, options)?;
+
+    Ok(entry.distinct.get(&column.to_lowercase()).cloned().unwrap_or_default())
+}
+
+/// 取得某欄位分頁後的不重複值 (使用快取)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 欄位名稱 (不分大小寫)
+/// - `offset`: 略過前幾個值
+/// - `limit`: 最多返回幾個值 (None 時返回全部)
+/// - `prefix`: 只保留以此開頭的值 (不分大小寫)
+/// ## 返回
+/// - `Result<DistinctPage, Error>`: 成功時返回該頁的值與符合條件的總數，失敗時返回錯誤
+pub fn distinct_values_page(app: AppHandle, filename: String, column: &str, offset: usize, limit: Option<usize>, prefix: Option<&str>) -> Result<DistinctPage, Error> {
+
+    let prefix = prefix.map(str::to_lowercase).unwrap_or_default();
+    let values: Vec<String> = cached_distinct_values(app, filename, column, &ReadOptions::default())?.into_iter()
+        .filter(|value| value.to_lowercase().starts_with(&prefix))
+        .collect();
+
+    let total = values.len();
+    let values = values.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect();
+
+    Ok(DistinctPage { values, total, offset })
+}
+
+/// 計算每個欄位的統計資料 (依欄位名稱排序，結果與記錄順序無關)
+/// ## 參數
+/// - `records`: 記錄
+/// ## 返回
+/// - `BTreeMap<String, ColumnProfile>`: 欄位名稱 => 統計資料
+pub fn profile_records(records: &[CsvRecord]) -> BTreeMap<String, ColumnProfile> {
+
+    let references: Vec<&CsvRecord> = records.iter().collect();
+
+    CsvRecord::headers().into_iter().map(|column| {
+        let filled = records.iter().filter(|record| !record.column_values(&column).unwrap_or_default().is_empty()).count();
+        let distinct = _distinct_values(&references, &column);
+        let numbers: Option<Vec<f64>> = distinct.iter().map(|value| value.trim().parse::<f64>().ok()).collect();
+        let numbers = numbers.filter(|numbers| !numbers.is_empty());
+
+        let profile = ColumnProfile {
+            distinct: distinct.len(),
+            filled,
+            completeness: if records.is_empty() { 0.0 } else { filled as f64 / records.len() as f64 },
+            min: numbers.as_ref().and_then(|numbers| numbers.iter().copied().reduce(f64::min)),
+            max: numbers.as_ref().and_then(|numbers| numbers.iter().copied().reduce(f64::max)),
+        };
+
+        (column, profile)
+    }).collect()
+}
+
+/// 把記錄攤平成只有純量值的物件 (給無法處理巢狀結構的試算表匯入工具)
+/// - 陣列的每個元素以「欄位.索引」為鍵，例如: {"Platform": ["Windows", "Linux"]} => {"Platform.0": "Windows", "Platform.1": "Linux"}
+/// - 物件以「欄位.子欄位」為鍵，純量值保留原本的鍵；依「.」拆開鍵、數字部分視為陣列索引即可還原
+/// - 空陣列、空物件保留原本的鍵並轉成 null (欄位不會消失)
+/// ## 參數
+/// - `values`: 每筆記錄的 JSON 物件
+/// ## 返回
+/// - `Vec<Value>`: 攤平後的物件
+pub fn flatten_records(values: Vec<Value>) -> Vec<Value> {
+
+    values.into_iter().map(|value| {
+        let mut flat = Map::new();
+        _flatten_value(String::new(), value, &mut flat);
+        Value::Object(flat)
+    }).collect()
+}
+
+/// 清除 CSV 快取 (包含解析後的記錄與各欄位的不重複值)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `usize`: 被清除的檔案數
+pub fn clear_csv_cache_entries(app: &AppHandle) -> usize {
+    app.state::<CsvCache>().clear()
+}
+
+/// 從 CSV 檔案讀取記錄，並附上每筆記錄在檔案中的起始行號
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項
 /// ## 返回
-/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回記錄的向量，失敗時返回錯誤
-pub fn read_csv_file(app: AppHandle, filename: String) -> Result<Vec<CsvRecord>, Error> {
+/// - `Result<Vec<(u64, CsvRecord)>, Error>`: 成功時返回 (行號, 記錄) 的向量，失敗時返回錯誤
+pub fn read_csv_file_with_lines(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<(u64, CsvRecord)>, Error> {
     let resource_path = _csv_file_path(&app, filename)?;
-    let records: Vec<CsvRecord> = _parse_csv_file(resource_path.to_string_lossy().to_string())?;
+    let records: Vec<(u64, CsvRecord)> = _parse_csv_file_with_lines(resource_path.to_string_lossy().to_string(), options, &mut ParseNotes::default())?;
 
     Ok(records)
 }
 
-/// 取得總Type的數值 => HashSet
+/// 只讀取指定的欄位 (以 StringRecord 索引取值，不反序列化成完整的 CsvRecord)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (`columns` 為要讀取的欄位)
+/// ## 返回
+/// - `Result<Vec<Value>, CsvError>`: 成功時返回只含指定欄位的物件，解析失敗時返回帶有行號 / 第幾筆資料的 CsvError::Parse
+pub fn read_csv_columns(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<Value>, CsvError> {
+    let resource_path = _csv_file_path(&app, filename)?;
+    _read_csv_columns(&resource_path, options)
+}
+
+/// 以標題列動態讀取任意欄位的 CSV 檔案 (不對應到 CsvRecord，全部的值都是字串)
+/// - 欄位不足的資料列補空字串，多出來的欄位會被忽略
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding / duplicateHeaders)
+/// ## 返回
+/// - `Result<(Vec<String>, Vec<Map<String, Value>>), CsvError>`: 成功時返回 (標題列, 每一列的物件)，解析失敗時返回帶有行號 / 第幾筆資料的 CsvError::Parse
+pub fn read_csv_dynamic_rows(app: AppHandle, filename: String, options: &ReadOptions) -> Result<(Vec<String>, Vec<Map<String, Value>>), CsvError> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .delimiter(_delimiter_byte(options)?)
+        .from_reader(_decoded_csv_source(&resource_path, options)?);
+
+    let mut headers = reader.headers().map_err(|error| _csv_parse_error(error, None, None))?.clone();
+    if headers.len() > 1 && headers.get(headers.len() - 1) == Some("") { headers.truncate(headers.len() - 1); }
+    _resolve_duplicate_headers(&mut headers, options.duplicate_headers)?;
+
+    let mut rows: Vec<Map<String, Value>> = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|error| _csv_parse_error(error, None, Some(&headers)))?;
+        let row = headers.iter().enumerate().map(|(index, header)| (header.to_string(), Value::from(record.get(index).unwrap_or_default()))).collect();
+        rows.push(row);
+    }
+
+    Ok((headers.iter().map(str::to_string).collect(), rows))
+}
+
+/// 逐批讀取 CSV 檔案的記錄 (每累積 `batch_size` 筆就交給 `on_batch`，記憶體用量不隨檔案大小成長)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `batch_size`: 每批的記錄數 (最少 1 筆)
+/// - `on_batch`: 處理一批記錄的函式，返回錯誤時會中止讀取
+/// ## 返回
+/// - `Result<usize, CsvError>`: 成功時返回總記錄數，解析失敗時返回帶有行號 / 第幾筆資料的 CsvError::Parse
+pub fn stream_csv_file<F>(app: AppHandle, filename: String, batch_size: usize, mut on_batch: F) -> Result<usize, CsvError> where F: FnMut(Vec<CsvRecord>) -> Result<(), Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let batch_size = batch_size.max(1);
+    let mut batch: Vec<CsvRecord> = Vec::with_capacity(batch_size);
+
+    let total = _for_each_csv_record(resource_path.to_string_lossy().to_string(), &ReadOptions::default(), &mut ParseNotes::default(), |_, record| {
+        batch.push(record);
+        if batch.len() < batch_size { return Ok(()); }
+        on_batch(std::mem::replace(&mut batch, Vec::with_capacity(batch_size)))
+    })?;
+
+    if !batch.is_empty() { on_batch(batch)?; }
+    Ok(total)
+}
+
+/// 依讀取選項的 offset / limit 取出一頁 (offset 超過總筆數時返回空陣列，limit 為 0 時不限制)
+/// ## 參數
+/// - `items`: 整理後的記錄
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Vec<T>`: 該頁的記錄
+pub fn paginate<T>(items: Vec<T>, options: &ReadOptions) -> Vec<T> {
+    let limit = if options.limit == 0 { usize::MAX } else { options.limit };
+    items.into_iter().skip(options.offset).take(limit).collect()
+}
+
+/// 依序讀取多個 CSV 檔案並合併記錄 (URL 相同 (不分大小寫) 的記錄只保留第一筆，沒有 URL 的記錄全部保留)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filenames`: CSV 檔案的名稱列表
+/// - `options`: 讀取選項 (套用到每個檔案)
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回合併後的記錄，任一檔案讀取失敗時返回帶有檔名的錯誤
+pub fn merged_csv_records(app: AppHandle, filenames: &[String], options: &ReadOptions) -> Result<Vec<CsvRecord>, Error> {
+
+    if filenames.is_empty() { return Err(Error::new(ErrorKind::InvalidInput, "Filenames cannot be empty")); }
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut merged: Vec<CsvRecord> = vec![];
+
+    for filename in filenames.iter() {
+
+        let records = read_csv_file(app.clone(), filename.clone(), options).map_err(|error| {
+            let message = format!("{}: {}", filename, error);
+            Error::new(Error::from(error).kind(), message)
+        })?;
+
+        for record in records {
+            let url = record.url.trim().to_lowercase();
+            if !url.is_empty() && !seen.insert(url) { continue; }
+            merged.push(record);
+        }
+    }
+
+    Ok(merged)
+}
+
+/// 搜尋 Name / Notes / URL 包含關鍵字的記錄 (不分大小寫，保持檔案中的順序)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `query`: 關鍵字 (去除前後空白，空字串時返回全部記錄)
+/// - `include_tags`: 是否也比對多值欄位 (Platform / Type / OS / Language / Category) 的每個值
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回符合的記錄，失敗時返回錯誤
+pub fn search_csv_records(app: AppHandle, filename: String, query: &str, include_tags: bool) -> Result<Vec<CsvRecord>, Error> {
+
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    let query = query.trim().to_lowercase();
+
+    if query.is_empty() { return Ok(records); }
+
+    let matches = |text: &str| text.to_lowercase().contains(&query);
+
+    Ok(records.into_iter().filter(|record| {
+        if matches(&record.name) || matches(&record.notes) || matches(&record.url) { return true; }
+        include_tags && CsvRecord::MULTI_VALUE_COLUMNS.iter().any(|column| record.multi_values(column).is_some_and(|values| values.iter().any(|value| matches(value))))
+    }).collect())
+}
+
+/// 依欄位排序記錄 (穩定排序，相同的值保持檔案中的順序)
+/// - Level 依數字排序，其他單值欄位 (Name / Notes / URL / Example) 不分大小寫排序；多值欄位無法排序
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
 /// - `filename`: CSV 檔案的名稱
+/// - `column`: 欄位名稱 (不分大小寫)，例如: "level"
+/// - `descending`: 是否由大到小排序
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回排序後的記錄，欄位未知或為多值欄位時返回 InvalidInput
+pub fn sort_csv_records(app: AppHandle, filename: String, column: &str, descending: bool) -> Result<Vec<CsvRecord>, Error> {
+
+    _check_column(column)?;
+
+    if CsvRecord::MULTI_VALUE_COLUMNS.iter().any(|name| name.eq_ignore_ascii_case(column)) {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("Column '{}' has multiple values and cannot be sorted", column)));
+    }
+
+    let mut records = read_csv_file(app, filename, &ReadOptions::default())?;
+
+    let compare = |record1: &CsvRecord, record2: &CsvRecord| -> Ordering {
+        if column.eq_ignore_ascii_case("level") { return record1.level.cmp(&record2.level); }
+
+        let text1 = record1.cell(column).unwrap_or_default().to_lowercase();
+        let text2 = record2.cell(column).unwrap_or_default().to_lowercase();
+        text1.cmp(&text2)
+    };
+
+    if descending {
+        records.sort_by(|record1, record2| compare(record2, record1));
+    } else {
+        records.sort_by(compare);
+    }
+
+    Ok(records)
+}
+
+/// 依讀取選項整理記錄 (排序 => 反轉順序)
+/// ## 參數
+/// - `records`: 解析後的記錄
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 整理後的記錄，locale 無法辨識時返回錯誤
+pub fn arrange_records(mut records: Vec<CsvRecord>, options: &ReadOptions) -> Result<Vec<CsvRecord>, Error> {
+
+    if options.sort_by_name {
+        let compare = name_comparator(options.locale.as_deref())?;
+        records.sort_by(|record1, record2| compare(record1.name.trim(), record2.name.trim()));
+    }
+
+    if options.reverse { records.reverse(); }
+    Ok(records)
+}
+
+/// 取得比較名稱用的函式
+/// - 沒有指定 locale 時: 先不分大小寫、再區分大小寫的逐字比較
+/// - 有指定 locale 時: 依該語系的排序規則 (ICU collation)，例如: "zh" 依拼音、"de" 讓 ä 排在 a 附近
+/// ## 參數
+/// - `locale`: 語系代碼，例如: "zh-TW"、"sv"
 /// ## 返回
-/// - `Result<HashSet<String>, Error>`: 成功時返回記錄的向量，失敗時返回錯誤
-pub fn read_type_set(app: AppHandle, filename: String) -> Result<HashSet<String>, Error> {
+/// - `Result<Box<dyn Fn(&str, &str) -> Ordering>, Error>`: 比較函式，locale 無法辨識時返回 InvalidInput
+pub fn name_comparator(locale: Option<&str>) -> Result<Box<dyn Fn(&str, &str) -> Ordering>, Error> {
 
-    let records = match read_csv_file(app, filename) {
-        Ok(records) => records,
-        Err(error) => return Err(error)
+    let locale = match locale.map(str::trim).filter(|locale| !locale.is_empty()) {
+        Some(locale) => locale,
+        None => return Ok(Box::new(|name1: &str, name2: &str| name1.to_lowercase().cmp(&name2.to_lowercase()).then(name1.cmp(name2)))),
     };
 
-    let mut type_set: HashSet<String> = HashSet::new();
+    let parsed: Locale = locale.parse()
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid locale '{}': {:?}", locale, error)))?;
+
+    let collator = Collator::try_new(&DataLocale::from(&parsed), CollatorOptions::new())
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Unsupported locale '{}': {:?}", locale, error)))?;
+
+    Ok(Box::new(move |name1: &str, name2: &str| collator.compare(name1, name2).then(name1.cmp(name2))))
+}
+
+/// 依讀取選項調整輸出的 JSON 形狀 (例如: 布林欄位、合併多值欄位、拆開 Notes)
+/// ## 參數
+/// - `records`: 整理後的記錄
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<Vec<Value>, Error>`: 成功時返回每筆記錄的 JSON 物件，布林欄位有無法辨識的值時返回錯誤
+pub fn shape_records(records: &[CsvRecord], options: &ReadOptions) -> Result<Vec<Value>, Error> {
+
+    let mut values: Vec<Value> = Vec::with_capacity(records.len());
+
     for record in records.iter() {
-        for r#type in record.r#type.clone() { type_set.insert(r#type.clone()); }
+
+        let mut value = serde_json::to_value(record).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+
+        if let Value::Object(object) = &mut value {
+            if let Err(message) = _apply_boolean_columns(object, options) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("record '{}': {}", record.name, message)));
+            }
+            if let Some(separator) = &options.join_multivalue { _join_multi_values(object, separator); }
+            if let Some(notes) = _split_notes(&record.notes, options) { object.insert(String::from("Notes"), notes); }
+        }
+
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+/// 取得總Type的數值 => 排序後的 Vec (與 read_distinct 的 Type 欄位相同)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (例如: 分隔符號)
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 成功時返回排序後的不重複值，失敗時返回錯誤
+pub fn read_type_set(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<String>, Error> {
+    cached_distinct_values(app, filename, "type", options)
+}
+
+/// 計算多值欄位每個值出現在幾筆記錄 (同一筆記錄內重複的值只算一次，略過空字串)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱 (不分大小寫)，例如: "type"、"platform"
+/// - `options`: 讀取選項 (例如: 分隔符號)
+/// ## 返回
+/// - `Result<HashMap<String, usize>, Error>`: 成功時返回 值 => 記錄數，失敗時返回錯誤
+pub fn facet_counts(app: AppHandle, filename: String, column: &str, options: &ReadOptions) -> Result<HashMap<String, usize>, Error> {
+
+    _check_multi_value_column(column)?;
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let entry = _cached_parse(&app, &resource_path, options)?;
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for record in entry.records.iter() {
+        let values: HashSet<&String> = record.multi_values(column).map(|values| values.iter().filter(|value| !value.is_empty()).collect()).unwrap_or_default();
+        for value in values { *counts.entry(value.clone()).or_insert(0) += 1; }
     }
 
-    return Ok(type_set);
+    Ok(counts)
 }
 
-/// 取得路徑資料夾內的檔案名稱列表 (排序)
+/// 取得路徑資料夾內的檔案名稱列表 (排序，不含子資料夾)
 /// ## 參數
 /// - `path`: 資料夾完整路徑
+/// - `extension`: 只保留此副檔名的檔案 (不分大小寫，可加上「.」)，例如: "csv"，None 或空字串代表不過濾
+/// - `include`: 只保留符合任一 glob 的檔名 (空陣列代表不過濾)，例如: ["app_*.csv"]
+/// - `exclude`: 排除符合任一 glob 的檔名，例如: ["*_backup.csv"]
 /// ## 返回
 /// - `Result<Vec<String>, Error>`: 檔案名稱列表
-pub fn folder_files(path: PathBuf) -> Result<Vec<String>, Error> {
+pub fn folder_files(path: PathBuf, extension: Option<&str>, include: &[String], exclude: &[String]) -> Result<Vec<String>, Error> {
     
     let mut file_names = Vec::new();
+    let include_set = _glob_set(include)?;
+    let exclude_set = _glob_set(exclude)?;
+    let extension = extension.map(|extension| extension.trim().trim_start_matches('.')).filter(|extension| !extension.is_empty());
     
     match read_dir(&path) {
         Err(error) => Err(error),
         Ok(entries) => {
             for entry in entries {
                 if let Ok(entry) = entry {
+                    if entry.path().is_dir() { continue; }
                     if let Some(name) = entry.file_name().to_str() {
+                        if let Some(extension) = extension { if !_has_extension(name, extension) { continue; } }
+                        if let Some(set) = &include_set { if !set.is_match(name) { continue; } }
+                        if let Some(set) = &exclude_set { if set.is_match(name) { continue; } }
                         file_names.push(name.to_string());
                     }
                 }
@@ -76,96 +501,3223 @@ pub fn folder_files(path: PathBuf) -> Result<Vec<String>, Error> {
     }
 }
 
-/// 初始化日誌系統 (會在應用程序資源目錄中創建 logs 目錄)
+/// 檢查檔案內各行的主要分隔符號是否一致 (例如: 部分行用逗號、部分行用分號)
 /// ## 參數
-/// - `app`: Tauri 應用程式的 handle
-pub fn logger_setting(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-
-    let log_dir = app.path().resolve("logs", BaseDirectory::Resource)?;
-    create_dir_all(&log_dir)?;
-
-    let log_file_name = format!("{}.log", Local::now().format("%Y%m%d"));
-    let log_file_path = log_dir.join(log_file_name);
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<Vec<usize>, Error>`: 主要分隔符號與整份檔案不同的行號 (從1開始)
+pub fn inconsistent_delimiter_lines(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<usize>, Error> {
 
-    ww_print!(format!("Log file location: {:?}", log_file_path));
+    let resource_path = _csv_file_path(&app, filename)?;
+    let mut content = String::new();
+    _decoded_csv_source(&resource_path, options)?.read_to_string(&mut content)?;
+    let dominants = _line_dominant_delimiters(&content, DELIMITER_SAMPLE_ROWS);
 
-    let log_file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file_path)?;
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for (_, delimiter) in dominants.iter() { *counts.entry(*delimiter).or_insert(0) += 1; }
 
-    Builder::from_env(Env::default().default_filter_or("debug"))
-        .format(|buffer, record| {
-            let mut style = buffer.style();
-            let level_color = match record.level() {
-                log::Level::Error => Color::Red,
-                log::Level::Warn => Color::Yellow,
-                log::Level::Info => Color::Green,
-                log::Level::Debug => Color::Blue,
-                log::Level::Trace => Color::Cyan,
-            };
+    let file_delimiter = match counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0))) {
+        Some((delimiter, _)) => *delimiter,
+        None => return Ok(Vec::new()),
+    };
 
-            writeln!(
-                buffer,
-                "{} [{}] {} - {}",
-                Local::now().format("%Y-%m-%d %H:%M:%S"),
-                style.set_color(level_color).value(record.level()),
-                record.target(),
-                record.args()
-            )
-        })
-        .target(env_logger::Target::Pipe(Box::new(log_file)))
-        .init();
+    let lines = dominants.into_iter()
+        .filter(|(_, delimiter)| *delimiter != file_delimiter)
+        .map(|(line, _)| line)
+        .collect();
 
-    Ok(())
+    Ok(lines)
 }
 
-/// 取得 CSV 檔案的完整路徑
+/// 檢查 Example 是否以規則指定的前綴開頭 (規則: Type => 前綴，前綴內的 `{name}` 會換成記錄的 Name)
+/// - 例如: {"CLI": "{name} "} 代表 Type 含有 CLI 的記錄，其 Example 必須以「Name + 空白」開頭
+/// - 沒有對應 Type 或沒有 Example 的記錄會被略過
 /// ## 參數
 /// - `app`: Tauri 應用的 AppHandle
 /// - `filename`: CSV 檔案的名稱
+/// - `rule_json`: 規則的 JSON 字串
 /// ## 返回
-/// - `Result<PathBuf, Error>`: 成功時返回檔案的完整路徑，失敗時返回錯誤
-fn _csv_file_path(app: &AppHandle, filename: String) -> Result<PathBuf, Error> {
-    if filename.is_empty() {
-        return Err(Error::new(
-            ErrorKind::InvalidInput,
-            "Filename cannot be empty",
-        ));
+/// - `Result<Vec<ExamplePrefixViolation>, Error>`: 不符合規則的記錄 (含行號)
+pub fn example_prefix_violations(app: AppHandle, filename: String, rule_json: String) -> Result<Vec<ExamplePrefixViolation>, Error> {
+
+    let rules: BTreeMap<String, String> = serde_json::from_str(&rule_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid rule JSON: {}", error)))?;
+
+    let mut violations: Vec<ExamplePrefixViolation> = Vec::new();
+
+    for (line, record) in read_csv_file_with_lines(app, filename, &ReadOptions::default())? {
+
+        let example = match &record.example {
+            Some(example) if !example.trim().is_empty() => example,
+            _ => continue,
+        };
+
+        let expected: Vec<String> = rules.iter()
+            .filter(|(r#type, _)| record.r#type.iter().any(|value| value.to_lowercase() == r#type.to_lowercase()))
+            .map(|(_, prefix)| prefix.replace("{name}", record.name.trim()))
+            .collect();
+
+        if expected.is_empty() || expected.iter().any(|prefix| example.starts_with(prefix.as_str())) { continue; }
+
+        violations.push(ExamplePrefixViolation { line, name: record.name.clone(), example: example.clone(), expected });
     }
 
-    let resource_path = match app.path().resolve("document", BaseDirectory::Resource) {
-        Ok(path) => path,
-        Err(error) => return Err(Error::new(ErrorKind::NotFound, error.to_string())),
-    };
+    Ok(violations)
+}
 
-    Ok(resource_path.as_path().join(filename))
+/// 取得 CsvRecord 的標題列 (CSV 格式字串，含換行)
+/// ## 返回
+/// - `Result<String, Error>`: 例如: "Name,Notes,URL,Level,...\n"
+pub fn csv_template_header() -> Result<String, Error> {
+
+    let mut writer = Writer::from_writer(Vec::new());
+    writer.write_record(CsvRecord::headers())?;
+
+    let bytes = writer.into_inner().map_err(|error| Error::other(error.to_string()))?;
+    String::from_utf8(bytes).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
 }
 
-/// 解析 CSV 檔案並返回記錄
+/// 建立只有標題列的空白 CSV 檔案 (檔案已存在時不覆蓋)
 /// ## 參數
-/// - `resource_path`: CSV 檔案的完整路徑
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入
 /// ## 返回
-/// - `Result<Vec<T>, Error>`: 成功時返回記錄的向量
-fn _parse_csv_file<T>(resource_path: String) -> Result<Vec<T>, Error> where T: DeserializeOwned + Debug {
-    if resource_path.is_empty() {
-        return Err(Error::new(
-            ErrorKind::InvalidInput,
-            "Resource path cannot be empty",
-        ));
-    }
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果
+pub fn write_csv_template(app: AppHandle, filename: String, dry_run: bool) -> Result<WriteReport, Error> {
+    let resource_path = _csv_file_path(&app, filename)?;
+    _write_csv_rows(&app, "create_csv_template", &resource_path, &CsvRecord::headers(), &[], false, dry_run)
+}
+
+/// 計算同一個多值欄位內，兩兩值一起出現在同一筆記錄的次數 (次數由多到少排序)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱，例如: "type"
+/// - `min_count`: 最少一起出現的記錄數
+/// ## 返回
+/// - `Result<Vec<TagPair>, Error>`: 成功時返回值組合與次數
+pub fn tag_pair_counts(app: AppHandle, filename: String, column: String, min_count: usize) -> Result<Vec<TagPair>, Error> {
 
-    let mut records: Vec<T> = Vec::new();
-    let opened_file = File::open(&resource_path)?;
-    let mut reader = Reader::from_reader(opened_file);
+    _check_multi_value_column(&column)?;
 
-    for result in reader.deserialize() {
-        match result {
-            Ok(record) => records.push(record),
-            Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+
+    for record in records.iter() {
+
+        let values: BTreeSet<&String> = record.multi_values(&column)
+            .map(|values| values.iter().filter(|value| !value.is_empty()).collect())
+            .unwrap_or_default();
+
+        let values: Vec<&String> = values.into_iter().collect();
+
+        for (index, first) in values.iter().enumerate() {
+            for second in values.iter().skip(index + 1) {
+                *counts.entry((first.to_string(), second.to_string())).or_insert(0) += 1;
+            }
         }
     }
 
-    Ok(records)
+    let mut pairs: Vec<TagPair> = counts.into_iter()
+        .filter(|(_, count)| *count >= min_count)
+        .map(|((first, second), count)| TagPair { first, second, count })
+        .collect();
+
+    pairs.sort_by(|pair1, pair2| pair2.count.cmp(&pair1.count).then(pair1.first.cmp(&pair2.first)).then(pair1.second.cmp(&pair2.second)));
+    Ok(pairs)
 }
 
+/// 把多值欄位展開成編號欄位後輸出成新的 CSV 檔案 (方便在 Excel 中使用)
+/// - 例如: Platform = "Windows,Linux" => Platform1 = "Windows", Platform2 = "Linux", Platform3 = ""
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `column`: 要展開的多值欄位名稱
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果 (含展開後的標題列)
+pub fn export_wide_csv(app: AppHandle, filename: String, out_filename: String, column: String, dry_run: bool) -> Result<WriteReport, Error> {
+
+    _check_multi_value_column(&column)?;
+
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+
+    let width = records.iter()
+        .map(|record| record.multi_values(&column).map_or(0, |values| values.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut headers: Vec<String> = Vec::new();
+
+    for header in CsvRecord::headers() {
+        if !header.eq_ignore_ascii_case(&column) { headers.push(header); continue; }
+        for index in 1..=width { headers.push(format!("{}{}", header, index)); }
+    }
+
+    let rows: Vec<Vec<String>> = records.iter().map(|record| {
+
+        let mut row: Vec<String> = Vec::new();
+
+        for header in CsvRecord::headers() {
+
+            if !header.eq_ignore_ascii_case(&column) { row.push(record.cell(&header).unwrap_or_default()); continue; }
+
+            let values = record.multi_values(&column).cloned().unwrap_or_default();
+            for index in 0..width { row.push(values.get(index).cloned().unwrap_or_default()); }
+        }
+
+        row
+    }).collect();
+
+    _write_csv_rows(&app, "export_wide", &out_path, &headers, &rows, false, dry_run)
+}
+
+/// 同時篩選欄位與資料列後輸出成新的 CSV 檔案 (例如: 只要 Linux CLI 工具的名稱與網址)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `options_json`: SubsetOptions 的 JSON 字串，例如: {"columns": ["Name", "URL"], "filters": {"os": ["Linux"]}}
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果 (含輸出的列數與欄位)
+pub fn export_subset_csv(app: AppHandle, filename: String, out_filename: String, options_json: String, dry_run: bool) -> Result<WriteReport, Error> {
+
+    let options: SubsetOptions = serde_json::from_str(&options_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid subset options JSON: {}", error)))?;
+
+    for column in options.columns.iter().chain(options.filters.keys()) { _check_column(column)?; }
+
+    let headers: Vec<String> = if options.columns.is_empty() {
+        CsvRecord::headers()
+    } else {
+        options.columns.iter()
+            .filter_map(|column| CsvRecord::headers().into_iter().find(|header| header.eq_ignore_ascii_case(column)))
+            .collect()
+    };
+
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+
+    let rows: Vec<Vec<String>> = records.iter()
+        .filter(|record| matches_facets(record, &options.filters))
+        .map(|record| headers.iter().map(|header| record.cell(header).unwrap_or_default()).collect())
+        .collect();
+
+    _write_csv_rows(&app, "export_subset", &out_path, &headers, &rows, false, dry_run)
+}
+
+/// 把前端傳來的記錄寫成 CSV 檔案 (多值欄位以逗號合併，可以再被 read_csv 讀回)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 輸出 CSV 檔案的名稱 (只能在 document 資料夾內)
+/// - `records`: 要寫入的記錄
+/// - `overwrite`: 檔案已存在時是否覆蓋
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果 (含最終路徑)，檔案已存在且未允許覆蓋時返回 AlreadyExists
+pub fn write_csv_records(app: AppHandle, filename: String, records: &[CsvRecord], overwrite: bool, dry_run: bool) -> Result<WriteReport, Error> {
+    let out_path = _csv_file_path(&app, filename)?;
+    _write_csv_rows(&app, "write_csv", &out_path, &CsvRecord::headers(), &_records_to_rows(records), overwrite, dry_run)
+}
+
+/// 把 CSV 檔案的記錄輸出成 config 資料夾內的 JSON 陣列 (縮排格式，欄位與 read_csv 的 result 相同)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 JSON 檔案的名稱 (寫入 config 資料夾)
+/// - `overwrite`: 檔案已存在時是否覆蓋
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果 (含最終路徑)，檔案已存在且未允許覆蓋時返回 AlreadyExists
+pub fn export_json_records(app: AppHandle, filename: String, out_filename: String, overwrite: bool, dry_run: bool) -> Result<WriteReport, Error> {
+
+    let out_path = _config_file_path(&app, out_filename)?;
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+
+    let report = WriteReport {
+        dry_run,
+        path: out_path.to_string_lossy().to_string(),
+        rows: records.len(),
+        columns: CsvRecord::headers(),
+    };
+
+    if !overwrite && out_path.exists() { return Err(Error::new(ErrorKind::AlreadyExists, format!("File already exists: {}", report.path))); }
+    if dry_run { return Ok(report); }
+
+    let json = serde_json::to_string_pretty(&records).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+    fs::write(&out_path, json + "\n")?;
+
+    if let Err(error) = append_change_log(&app, &out_path, "export_csv_to_json", report.rows) { warn!("Failed to write change log for {:?}: {}", out_path, error); }
+    Ok(report)
+}
+
+/// 依 Category 把記錄輸出成 xlsx，每個 Category 一個工作表 (有多個 Category 的記錄會出現在每個相關的工作表)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 xlsx 檔案的名稱
+/// - `overwrite`: 檔案已存在時是否覆蓋
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<XlsxReport, Error>`: 成功時返回每個工作表的名稱與列數，輸出檔案已存在且未允許覆蓋時返回 AlreadyExists
+pub fn export_xlsx_category_sheets(app: AppHandle, filename: String, out_filename: String, overwrite: bool, dry_run: bool) -> Result<XlsxReport, Error> {
+
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let headers = CsvRecord::headers();
+
+    let mut groups: BTreeMap<String, Vec<&CsvRecord>> = BTreeMap::new();
+    for record in records.iter() {
+        let categories = record.column_values("Category").unwrap_or_default();
+        if categories.is_empty() { groups.entry(UNCATEGORIZED_SHEET.to_string()).or_default().push(record); }
+
+        for category in BTreeSet::from_iter(categories) { groups.entry(category).or_default().push(record); }
+    }
+
+    let mut used_names: HashSet<String> = HashSet::new();
+    let sheets: Vec<(XlsxSheet, Vec<&CsvRecord>)> = groups.into_iter()
+        .map(|(category, records)| (XlsxSheet { name: _sheet_name(&category, &mut used_names), category, rows: records.len() }, records))
+        .collect();
+
+    let report = XlsxReport {
+        write: WriteReport {
+            dry_run,
+            path: out_path.to_string_lossy().to_string(),
+            rows: sheets.iter().map(|(sheet, _)| sheet.rows).sum(),
+            columns: headers.clone(),
+        },
+        sheets: sheets.iter().map(|(sheet, _)| sheet.clone()).collect(),
+    };
+
+    if !overwrite && out_path.exists() { return Err(Error::new(ErrorKind::AlreadyExists, format!("File already exists: {}", report.write.path))); }
+    if dry_run { return Ok(report); }
+
+    _write_xlsx_sheets(&out_path, &headers, &sheets).map_err(|error| Error::other(error.to_string()))?;
+
+    if let Err(error) = append_change_log(&app, &out_path, "export_xlsx_by_category", report.write.rows) { warn!("Failed to write change log for {:?}: {}", out_path, error); }
+    Ok(report)
+}
+
+/// 把 CSV 檔案改寫成固定格式 (同樣的資料永遠得到同樣的位元組，方便 git diff)
+/// - 標題列: CsvRecord 的全部欄位，依宣告順序 (Name, Notes, URL, Level, Example, Platform, Type, OS, Language, Category)
+/// - 資料列: 依 Name 排序 (先不分大小寫、再區分大小寫、最後依 URL)
+/// - 欄位值: 去除前後空白；多值欄位去除空白值後以「,」合併 (不加空白，保留原本順序)
+/// - 格式: UTF-8 無 BOM、以 \n 換行 (最後一行也有)、只在必要時加雙引號
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (可以與來源相同，已存在時會覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果
+pub fn canonicalize_csv(app: AppHandle, filename: String, out_filename: String, dry_run: bool) -> Result<WriteReport, Error> {
+
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let headers = CsvRecord::headers();
+    let rows = _canonical_rows(&records, &headers);
+
+    _write_csv_rows(&app, "canonicalize_file", &out_path, &headers, &rows, true, dry_run)
+}
+
+/// 把多個多值欄位合併成一個新的欄位後輸出 (例如: Type + Category + Language => Tags)
+/// - 新欄位放在第一個來源欄位的位置，值不分大小寫去除重複 (保留第一次出現的寫法)，來源欄位會被移除
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `source_columns_json`: 來源多值欄位的 JSON 陣列字串，例如: ["type", "category", "language"]
+/// - `target_column`: 新欄位的名稱，例如: "Tags"
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<MergeReport, Error>`: 成功時返回寫入結果與來源欄位有重疊值的記錄數
+pub fn merge_csv_columns(app: AppHandle, filename: String, source_columns_json: String, target_column: String, out_filename: String, dry_run: bool) -> Result<MergeReport, Error> {
+
+    let source_columns: Vec<String> = serde_json::from_str(&source_columns_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid source columns JSON: {}", error)))?;
+
+    if source_columns.is_empty() { return Err(Error::new(ErrorKind::InvalidInput, "Source columns cannot be empty")); }
+    for column in source_columns.iter() { _check_multi_value_column(column)?; }
+
+    let target_column = target_column.trim().to_string();
+    let is_source = |header: &str| source_columns.iter().any(|column| column.eq_ignore_ascii_case(header));
+
+    if target_column.is_empty() { return Err(Error::new(ErrorKind::InvalidInput, "Target column cannot be empty")); }
+    if CsvRecord::headers().iter().any(|header| header.eq_ignore_ascii_case(&target_column) && !is_source(header)) {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("Target column '{}' already exists", target_column)));
+    }
+
+    let mut headers: Vec<String> = Vec::new();
+
+    for header in CsvRecord::headers() {
+        if !is_source(&header) { headers.push(header); continue; }
+        if !headers.contains(&target_column) { headers.push(target_column.clone()); }
+    }
+
+    let records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let mut overlapping = 0;
+
+    let rows: Vec<Vec<String>> = records.iter().map(|record| {
+
+        let mut merged: Vec<String> = Vec::new();
+        let mut has_overlap = false;
+
+        for column in source_columns.iter() {
+
+            let mut seen_in_column: HashSet<String> = HashSet::new();
+
+            for value in record.column_values(column).unwrap_or_default() {
+                if !seen_in_column.insert(value.to_lowercase()) { continue; }
+                if merged.iter().any(|existing| existing.to_lowercase() == value.to_lowercase()) { has_overlap = true; continue; }
+                merged.push(value);
+            }
+        }
+
+        if has_overlap { overlapping += 1; }
+
+        headers.iter().map(|header| {
+            if *header == target_column { join_multi_value(&merged) } else { record.cell(header).unwrap_or_default() }
+        }).collect()
+    }).collect();
+
+    let write = _write_csv_rows(&app, "merge_columns", &out_path, &headers, &rows, false, dry_run)?;
+    Ok(MergeReport { write, overlapping })
+}
+
+/// 把 http:// 開頭的 URL 改成 https:// 後輸出成新的 CSV 檔案
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<ChangeReport, Error>`: 成功時返回寫入結果與改寫的筆數
+pub fn upgrade_https_csv(app: AppHandle, filename: String, out_filename: String, dry_run: bool) -> Result<ChangeReport, Error> {
+
+    let mut records = read_csv_file(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let mut changed = 0;
+
+    for record in records.iter_mut() {
+        let url = record.url.trim();
+        if _url_scheme(url) != "http" { continue; }
+        record.url = format!("https:{}", &url["http:".len()..]);
+        changed += 1;
+    }
+
+    let write = _write_csv_rows(&app, "upgrade_https", &out_path, &CsvRecord::headers(), &_records_to_rows(&records), false, dry_run)?;
+    Ok(ChangeReport { write, changed })
+}
+
+/// 正規化 URL 後輸出成新的 CSV 檔案 (主機名稱轉小寫、移除預設連接埠，可選擇移除結尾的斜線)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `strip_trailing_slash`: 移除路徑結尾的斜線 (有 query 或 fragment 時不處理)
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<UrlNormalizeReport, Error>`: 成功時返回寫入結果、改寫的筆數與無法解析 (保留原樣) 的 URL
+pub fn normalize_csv_urls(app: AppHandle, filename: String, out_filename: String, strip_trailing_slash: bool, dry_run: bool) -> Result<UrlNormalizeReport, Error> {
+
+    let mut records = read_csv_file_with_lines(app.clone(), filename, &ReadOptions::default())?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let mut changed = 0;
+    let mut unparseable: Vec<InvalidUrl> = vec![];
+
+    for (line, record) in records.iter_mut() {
+        let url = record.url.trim();
+        if url.is_empty() { continue; }
+
+        let parsed = match Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                unparseable.push(InvalidUrl { line: *line, name: record.name.clone(), url: record.url.clone(), message: error.to_string() });
+                continue;
+            }
+        };
+
+        let mut normalized = parsed.to_string();
+        if strip_trailing_slash && parsed.query().is_none() && parsed.fragment().is_none() && normalized.ends_with('/') { normalized.pop(); }
+
+        if normalized == record.url { continue; }
+        record.url = normalized;
+        changed += 1;
+    }
+
+    let records: Vec<CsvRecord> = records.into_iter().map(|(_, record)| record).collect();
+    let write = _write_csv_rows(&app, "normalize_urls", &out_path, &CsvRecord::headers(), &_records_to_rows(&records), false, dry_run)?;
+
+    Ok(UrlNormalizeReport { write, changed, unparseable })
+}
+
+/// 移除完全沒有資料的欄位後輸出成新的 CSV 檔案 (以原始字串讀取，任何欄位格式的檔案都可以使用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 來源 CSV 檔案的名稱
+/// - `out_filename`: 輸出 CSV 檔案的名稱 (檔案已存在時不覆蓋)
+/// - `dry_run`: 只回報預計的變更，不實際寫入
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果 (含保留下來的欄位)
+pub fn drop_empty_csv_columns(app: AppHandle, filename: String, out_filename: String, dry_run: bool, options: &ReadOptions) -> Result<WriteReport, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let out_path = _csv_file_path(&app, out_filename)?;
+    let (headers, records) = _read_raw_records(&resource_path, options)?;
+
+    let kept: Vec<usize> = (0..headers.len()).filter(|index| !_is_empty_column(&records, *index)).collect();
+    let kept_headers: Vec<String> = kept.iter().map(|index| headers[*index].to_string()).collect();
+
+    let rows: Vec<Vec<String>> = records.iter()
+        .map(|record| kept.iter().map(|index| record.get(*index).unwrap_or_default().to_string()).collect())
+        .collect();
+
+    _write_csv_rows(&app, "drop_empty_columns", &out_path, &kept_headers, &rows, false, dry_run)
+}
+
+/// 取得符合篩選條件的記錄中，某個欄位的不重複值 (排序，可用於連動的下拉選單)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 要取值的欄位名稱，例如: "type"
+/// - `filters_json`: 篩選條件的 JSON 字串，例如: {"os": ["Linux"]}，空字串或 {} 代表不篩選
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 成功時返回排序後的不重複值
+pub fn distinct_values_where(app: AppHandle, filename: String, column: String, filters_json: String) -> Result<Vec<String>, Error> {
+
+    let filters = parse_facet_filters(&filters_json)?;
+    _check_column(&column)?;
+
+    if filters.is_empty() { return cached_distinct_values(app, filename, &column, &ReadOptions::default()); }
+
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    let matched: Vec<&CsvRecord> = records.iter().filter(|record| matches_facets(record, &filters)).collect();
+
+    Ok(_distinct_values(&matched, &column))
+}
+
+/// 讀取多值欄位與指定值有交集的記錄 (不分大小寫，values 為空陣列時返回全部)，可再加上其他欄位的篩選條件
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `field`: 多值欄位名稱 (不分大小寫)，例如: "type"
+/// - `values`: 要包含的值 (符合任一個即可)
+/// - `filters_json`: 其他篩選條件的 JSON 字串 (可包含 exclude，同一欄位時與 values 合併)，空字串代表不篩選
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回符合的記錄 (依檔案順序)
+pub fn filter_csv_records(app: AppHandle, filename: String, field: String, values: Vec<String>, filters_json: &str) -> Result<Vec<CsvRecord>, Error> {
+
+    let filters = _field_filters(field, values, filters_json)?;
+
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    Ok(records.into_iter().filter(|record| matches_facets(record, &filters)).collect())
+}
+
+/// 把多值欄位要包含的值併入其他篩選條件 (同一欄位時合併 include，保留 exclude)
+/// ## 參數
+/// - `field`: 多值欄位名稱 (不分大小寫)
+/// - `values`: 要包含的值
+/// - `filters_json`: 其他篩選條件的 JSON 字串，空字串代表沒有其他條件
+/// ## 返回
+/// - `Result<FacetFilters, Error>`: 欄位不是多值欄位、JSON 格式錯誤或欄位名稱未知時返回 InvalidInput
+fn _field_filters(field: String, values: Vec<String>, filters_json: &str) -> Result<FacetFilters, Error> {
+
+    _check_multi_value_column(&field)?;
+
+    let mut filters = parse_facet_filters(filters_json)?;
+    let existing = filters.keys().find(|column| column.eq_ignore_ascii_case(&field)).cloned();
+
+    let filter = match existing.and_then(|column| filters.remove(&column)) {
+        Some(filter) => FacetFilter::Rule(FacetRule { include: [values, filter.include().to_vec()].concat(), exclude: filter.exclude().to_vec() }),
+        None => FacetFilter::Include(values),
+    };
+    filters.insert(field, filter);
+
+    Ok(filters)
+}
+
+/// 把篩選條件的 JSON 字串轉成 FacetFilters (會檢查欄位名稱)
+/// ## 參數
+/// - `filters_json`: 篩選條件的 JSON 字串，空字串代表不篩選
+/// ## 返回
+/// - `Result<FacetFilters, Error>`: JSON 格式錯誤或欄位名稱未知時返回 InvalidInput
+pub fn parse_facet_filters(filters_json: &str) -> Result<FacetFilters, Error> {
+
+    if filters_json.trim().is_empty() { return Ok(FacetFilters::new()); }
+
+    let filters: FacetFilters = serde_json::from_str(filters_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid filters JSON: {}", error)))?;
+
+    for column in filters.keys() { _check_column(column)?; }
+    Ok(filters)
+}
+
+/// 檢查記錄是否符合所有的篩選條件 (不分大小寫)
+/// ## 參數
+/// - `record`: 單筆記錄
+/// - `filters`: 篩選條件
+/// ## 返回
+/// - `bool`: 每個欄位都至少有一個值符合 (且沒有任何要排除的值) 時返回 true
+pub fn matches_facets(record: &CsvRecord, filters: &FacetFilters) -> bool {
+
+    filters.iter().all(|(column, filter)| {
+
+        let values = record.column_values(column).unwrap_or_default();
+        let contains_any = |targets: &[String]| values.iter().any(|value| targets.iter().any(|target| target.trim().to_lowercase() == value.to_lowercase()));
+
+        (filter.include().is_empty() || contains_any(filter.include())) && !contains_any(filter.exclude())
+    })
+}
+
+/// 量測解析 CSV 檔案的速度 (解析後的記錄不會保留，只計算解析本身的成本)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `columns`: 另外量測只解析這些欄位的耗時 (空陣列時不量測)
+/// ## 返回
+/// - `Result<ParseBenchmark, Error>`: 成功時返回筆數、位元組數、耗時與每秒筆數 / MB
+pub fn benchmark_csv_parse(app: AppHandle, filename: String, columns: &[String]) -> Result<ParseBenchmark, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let bytes = resource_path.metadata()?.len();
+
+    let start = Instant::now();
+    let rows = _for_each_csv_record::<CsvRecord, _>(resource_path.to_string_lossy().to_string(), &ReadOptions::default(), &mut ParseNotes::default(), |_, _| Ok(()))?;
+    let elapsed = start.elapsed().as_secs_f64();
+
+    let columns_elapsed = if columns.is_empty() { None } else {
+        let options = ReadOptions { columns: columns.to_vec(), ..ReadOptions::default() };
+        let start = Instant::now();
+        _read_csv_columns(&resource_path, &options)?;
+        Some(start.elapsed().as_secs_f64())
+    };
+
+    let per_second = |amount: f64| if elapsed > 0.0 { amount / elapsed } else { 0.0 };
+    let benchmark = ParseBenchmark {
+        rows,
+        bytes,
+        elapsed_ms: elapsed * 1000.0,
+        rows_per_sec: per_second(rows as f64),
+        mb_per_sec: per_second(bytes as f64 / (1024.0 * 1024.0)),
+        columns_elapsed_ms: columns_elapsed.map(|seconds| seconds * 1000.0),
+        speedup: columns_elapsed.filter(|seconds| *seconds > 0.0).map(|seconds| elapsed / seconds),
+    };
+
+    info!("Parse benchmark {:?}: {:?}", resource_path, benchmark);
+    Ok(benchmark)
+}
+
+/// 依 config 資料夾內的規則檔讀取記錄 (規則檔會在解析 CSV 之前先檢查)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `rules_filename`: 規則檔的名稱 (config 資料夾內的 JSON 檔)
+/// ## 返回
+/// - `Result<Vec<CsvRecord>, Error>`: 成功時返回符合規則的記錄
+pub fn read_csv_file_with_rules(app: AppHandle, filename: String, rules_filename: String) -> Result<Vec<CsvRecord>, Error> {
+
+    let rules = read_record_rules(&app, rules_filename)?;
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+
+    Ok(records.into_iter().filter(|record| matches_rules(record, &rules)).collect())
+}
+
+/// 讀取並檢查 config 資料夾內的規則檔
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `rules_filename`: 規則檔的名稱
+/// ## 返回
+/// - `Result<RecordRules, Error>`: 檔案不存在、JSON 格式錯誤、欄位未知或 Level 範圍錯誤時返回錯誤
+pub fn read_record_rules(app: &AppHandle, rules_filename: String) -> Result<RecordRules, Error> {
+
+    let rules_path = _config_file_path(app, rules_filename)?;
+    let content = read_to_string(&rules_path)?;
+
+    let rules: RecordRules = serde_json::from_str(&content)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid rules file {:?}: {}", rules_path, error)))?;
+
+    for column in rules.filters.keys() { _check_column(column)?; }
+
+    if let (Some(min_level), Some(max_level)) = (rules.min_level, rules.max_level) {
+        if min_level > max_level {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid rules file {:?}: minLevel {} is greater than maxLevel {}", rules_path, min_level, max_level)));
+        }
+    }
+
+    Ok(rules)
+}
+
+/// 檢查記錄是否符合規則 (Level 範圍與欄位篩選條件)
+/// ## 參數
+/// - `record`: 單筆記錄
+/// - `rules`: 資料列規則
+/// ## 返回
+/// - `bool`: 全部條件都符合時返回 true
+pub fn matches_rules(record: &CsvRecord, rules: &RecordRules) -> bool {
+    if rules.min_level.is_some_and(|level| record.level < level) { return false; }
+    if rules.max_level.is_some_and(|level| record.level > level) { return false; }
+    matches_facets(record, &rules.filters)
+}
+
+/// 檢查複合鍵是否唯一 (例如: Name + URL)，找出鍵值相同的記錄
+/// - 比對時不分大小寫並去除前後空白，多值欄位先排序再以逗號合併後比對
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `key_columns_json`: 鍵欄位的 JSON 陣列字串，例如: ["name", "url"]
+/// ## 返回
+/// - `Result<Vec<DuplicateKeyGroup>, Error>`: 重複的群組 (依第一次出現的行號排序)，全部唯一時返回空陣列
+pub fn duplicate_key_groups(app: AppHandle, filename: String, key_columns_json: String) -> Result<Vec<DuplicateKeyGroup>, Error> {
+
+    let key_columns = _parse_key_columns(&key_columns_json)?;
+    let records = read_csv_file_with_lines(app, filename, &ReadOptions::default())?;
+    Ok(_duplicate_key_groups(&records, &key_columns))
+}
+
+/// 只讀取標題列與第一筆資料 (原始字串，任何欄位格式的檔案都可以使用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<CsvPeek, Error>`: 成功時返回標題列與第一筆資料 (只有標題列時資料為空陣列)
+pub fn peek_csv_file(app: AppHandle, filename: String, options: &ReadOptions) -> Result<CsvPeek, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let mut reader = _open_raw_reader(&resource_path, options)?;
+
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers.iter().map(|header| header.to_string()).collect(),
+        Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+    };
+
+    let sample: Vec<String> = match reader.records().next() {
+        Some(Ok(record)) => record.iter().map(|field| field.to_string()).collect(),
+        Some(Err(error)) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+        None => Vec::new(),
+    };
+
+    Ok(CsvPeek { headers, sample })
+}
+
+/// 取得檔案大小、修改時間、標題列與資料列數 (以 ByteRecord 逐行計數，不反序列化，大檔案也很快)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<CsvMetadata, Error>`: 成功時返回檔案資訊，檔案是空的或沒有標題列時返回 InvalidData
+pub fn csv_file_metadata(app: AppHandle, filename: String, options: &ReadOptions) -> Result<CsvMetadata, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let metadata = resource_path.metadata()?;
+
+    if metadata.len() == 0 { return Err(Error::new(ErrorKind::InvalidData, format!("File is empty: {:?}", resource_path))); }
+
+    let mut reader = _open_raw_reader(&resource_path, options)?;
+
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers.iter().map(|header| header.to_string()).collect(),
+        Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+    };
+
+    if headers.iter().all(|header| header.trim().is_empty()) {
+        return Err(Error::new(ErrorKind::InvalidData, format!("File has no header row: {:?}", resource_path)));
+    }
+
+    let mut record = ByteRecord::new();
+    let mut rows = 0;
+
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(true) => rows += 1,
+            Ok(false) => break,
+            Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+        }
+    }
+
+    let modified: DateTime<Local> = metadata.modified()?.into();
+
+    Ok(CsvMetadata {
+        size: metadata.len(),
+        modified: modified.format("%Y-%m-%d %H:%M:%S").to_string(),
+        headers,
+        rows,
+    })
+}
+
+/// 找出 URL 不是 https 的記錄 (http、ftp 或沒有 scheme，空白 URL 不檢查)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<Vec<UrlViolation>, Error>`: 不符合的記錄 (含行號與 scheme，沒有 scheme 時為空字串)
+pub fn non_https_urls(app: AppHandle, filename: String) -> Result<Vec<UrlViolation>, Error> {
+
+    let records = read_csv_file_with_lines(app, filename, &ReadOptions::default())?;
+
+    let violations = records.into_iter()
+        .filter(|(_, record)| !record.url.trim().is_empty())
+        .filter_map(|(line, record)| {
+            let scheme = _url_scheme(record.url.trim());
+            if scheme == "https" { return None; }
+            Some(UrlViolation { line, name: record.name, url: record.url, scheme })
+        })
+        .collect();
+
+    Ok(violations)
+}
+
+/// 找出文字欄位含有禁用詞的記錄 (不分大小寫，不會修改檔案)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `terms`: 禁用詞列表 (空白的詞會被忽略)
+/// - `all_fields`: true 時檢查所有文字欄位 (Name、Notes、URL、Example 與多值欄位)，false 時只檢查 Notes
+/// ## 返回
+/// - `Result<Vec<ForbiddenMatch>, Error>`: 符合的記錄、欄位與禁用詞
+pub fn forbidden_term_matches(app: AppHandle, filename: String, terms: &[String], all_fields: bool) -> Result<Vec<ForbiddenMatch>, Error> {
+
+    let terms: Vec<(String, String)> = terms.iter()
+        .map(|term| term.trim())
+        .filter(|term| !term.is_empty())
+        .map(|term| (term.to_string(), term.to_lowercase()))
+        .collect();
+
+    let fields: Vec<String> = if all_fields {
+        CsvRecord::headers().into_iter().filter(|header| header != "Level").collect()
+    } else {
+        vec![String::from("Notes")]
+    };
+
+    let records = read_csv_file_with_lines(app, filename, &ReadOptions::default())?;
+    let mut matches: Vec<ForbiddenMatch> = vec![];
+
+    for (line, record) in records.iter() {
+        for field in fields.iter() {
+            let text = record.cell(field).unwrap_or_default().to_lowercase();
+
+            for (term, lowercase_term) in terms.iter() {
+                if !text.contains(lowercase_term.as_str()) { continue; }
+                matches.push(ForbiddenMatch { line: *line, name: record.name.clone(), field: field.clone(), term: term.clone() });
+            }
+        }
+    }
+
+    Ok(matches)
+}
+
+/// 讀取 config 資料夾內的禁用詞列表 (JSON 字串陣列)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `terms_filename`: 禁用詞檔案的名稱，例如: "forbiddenTerms.json"
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 檔案不存在或 JSON 格式錯誤時返回錯誤
+pub fn read_forbidden_terms(app: &AppHandle, terms_filename: String) -> Result<Vec<String>, Error> {
+
+    let terms_path = _config_file_path(app, terms_filename)?;
+    let content = read_to_string(&terms_path)?;
+
+    serde_json::from_str(&content)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid terms file {:?}: {}", terms_path, error)))
+}
+
+/// 找出多值欄位的值數量超過上限的記錄 (空白的值不計算)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `column`: 多值欄位名稱 (不分大小寫)，例如: "Type"
+/// - `max`: 值數量的上限
+/// ## 返回
+/// - `Result<Vec<TagLimitViolation>, Error>`: 超過上限的記錄 (全部符合時為空陣列)
+pub fn tag_limit_violations(app: AppHandle, filename: String, column: String, max: usize) -> Result<Vec<TagLimitViolation>, Error> {
+
+    _check_multi_value_column(&column)?;
+
+    let records = read_csv_file_with_lines(app, filename, &ReadOptions::default())?;
+
+    let violations = records.into_iter()
+        .filter_map(|(line, record)| {
+            let count = record.column_values(&column).unwrap_or_default().len();
+            if count <= max { return None; }
+            Some(TagLimitViolation { line, name: record.name, count, max })
+        })
+        .collect();
+
+    Ok(violations)
+}
+
+/// 找出名稱列表中，在檔案內沒有對應記錄的名稱 (Name 不分大小寫比對，依輸入順序返回)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `names_json`: 名稱的 JSON 陣列字串，例如: ["lsof", "kill"]
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 找不到的名稱
+pub fn missing_names(app: AppHandle, filename: String, names_json: String) -> Result<Vec<String>, Error> {
+
+    let names: Vec<String> = serde_json::from_str(&names_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid names JSON: {}", error)))?;
+
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    let existing: HashSet<String> = records.iter().map(|record| record.name.trim().to_lowercase()).collect();
+
+    Ok(names.into_iter().filter(|name| !existing.contains(&name.trim().to_lowercase())).collect())
+}
+
+/// 把檔案內容整理成一行摘要 (只讀取一次資料)
+/// - 例如: "142 records, 8 types, levels 1–5, 3 platforms"，沒有記錄時為 "0 records"
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<CsvSummary, Error>`: 成功時返回摘要
+pub fn summarize_csv_file(app: AppHandle, filename: String) -> Result<CsvSummary, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let mut types: HashSet<String> = HashSet::new();
+    let mut platforms: HashSet<String> = HashSet::new();
+    let mut levels: Option<(u8, u8)> = None;
+
+    let records = _for_each_csv_record::<CsvRecord, _>(resource_path.to_string_lossy().to_string(), &ReadOptions::default(), &mut ParseNotes::default(), |_, record| {
+        types.extend(record.column_values("type").unwrap_or_default());
+        platforms.extend(record.column_values("platform").unwrap_or_default());
+        levels = Some(levels.map_or((record.level, record.level), |(min, max)| (min.min(record.level), max.max(record.level))));
+        Ok(())
+    })?;
+
+    let text = match levels {
+        None => format!("{} records", records),
+        Some((min, max)) => format!("{} records, {} types, levels {}–{}, {} platforms", records, types.len(), min, max, platforms.len()),
+    };
+
+    Ok(CsvSummary {
+        records,
+        types: types.len(),
+        platforms: platforms.len(),
+        min_level: levels.map(|(min, _)| min),
+        max_level: levels.map(|(_, max)| max),
+        text,
+    })
+}
+
+/// 在 logs/changes 資料夾內，為檔案加上一筆變更記錄 (每個檔案一個 JSON Lines 檔)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `path`: 被修改的檔案路徑
+/// - `operation`: 操作名稱
+/// - `rows`: 影響的資料列數
+/// ## 返回
+/// - `Result<(), Error>`: 寫入失敗時返回錯誤
+pub fn append_change_log(app: &AppHandle, path: &Path, operation: &str, rows: usize) -> Result<(), Error> {
+
+    let filename = match path.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid file path: {:?}", path))),
+    };
+
+    let log_path = _change_log_path(app, &filename)?;
+
+    let entry = ChangeLogEntry {
+        timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        operation: operation.to_string(),
+        rows,
+    };
+
+    let line = serde_json::to_string(&entry).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+    let mut log_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+
+    writeln!(log_file, "{}", line)
+}
+
+/// 讀取檔案的變更記錄 (由舊到新)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 檔案的名稱
+/// ## 返回
+/// - `Result<Vec<ChangeLogEntry>, Error>`: 成功時返回變更記錄，沒有記錄時返回空陣列
+pub fn read_change_log_entries(app: AppHandle, filename: String) -> Result<Vec<ChangeLogEntry>, Error> {
+
+    let log_path = _change_log_path(&app, &filename)?;
+    if !log_path.exists() { return Ok(Vec::new()); }
+
+    read_to_string(log_path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string())))
+        .collect()
+}
+
+/// 計算每筆記錄文字欄位的總字元數 (順序與記錄相同，供前端預估列表項目大小)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<Vec<usize>, Error>`: 成功時返回每筆記錄的字元數，失敗時返回錯誤
+pub fn row_text_lengths(app: AppHandle, filename: String) -> Result<Vec<usize>, Error> {
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    Ok(records.iter().map(CsvRecord::text_length).collect())
+}
+
+/// 找出標題列中重複的欄位名稱 (去除前後空白後比較，空白的欄位名稱不計算)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 重複的欄位名稱 (依第一次重複出現的順序，沒有重複時為空陣列)
+pub fn duplicate_header_names(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<String>, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let mut reader = _open_raw_reader(&resource_path, options)?;
+    let headers = reader.headers().map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+
+    Ok(_duplicate_headers(headers))
+}
+
+/// 驗證單一 CSV 檔案 (重複的標題、分隔符號不一致、解析錯誤、非 https 的 URL、重複的 Name + URL)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 找到的問題 (沒有問題時為空陣列)，檔案無法讀取時返回錯誤
+pub fn validate_csv_file(app: AppHandle, filename: String) -> Result<Vec<String>, Error> {
+
+    let mut problems: Vec<String> = vec![];
+
+    for line in inconsistent_delimiter_lines(app.clone(), filename.clone(), &ReadOptions::default())? {
+        problems.push(format!("Line {}: inconsistent delimiter", line));
+    }
+
+    let duplicates = duplicate_header_names(app.clone(), filename.clone(), &ReadOptions::default())?;
+    if !duplicates.is_empty() {
+        problems.push(format!("Duplicate header names: {}", duplicates.join(", ")));
+        return Ok(problems);
+    }
+
+    let resource_path = _csv_file_path(&app, filename.clone())?;
+    if let Err(error) = _parse_csv_file::<CsvRecord>(resource_path.to_string_lossy().to_string(), &ReadOptions::default(), &mut ParseNotes::default()) {
+        problems.push(format!("Parse error: {}", error));
+        return Ok(problems);
+    }
+
+    for violation in non_https_urls(app.clone(), filename.clone())? {
+        problems.push(format!("Line {}: URL is not https: {}", violation.line, violation.url));
+    }
+
+    for group in duplicate_key_groups(app, filename, String::from(r#"["name", "url"]"#))? {
+        let lines: Vec<String> = group.lines.iter().map(|line| line.to_string()).collect();
+        problems.push(format!("Lines {}: duplicate Name + URL: {}", lines.join(", "), group.key.join(" + ")));
+    }
+
+    Ok(problems)
+}
+
+/// 平行驗證 document 資料夾 (app.json 的 documentDir) 內所有的 .csv 檔案 (單一檔案失敗或 panic 不會中斷其他檔案)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `concurrency`: 同時驗證的檔案數上限 (至少 1)
+/// ## 返回
+/// - `Result<Vec<FileValidation>, Error>`: 每個檔案的驗證結果 (依檔名排序)
+pub fn validate_folder_files(app: AppHandle, concurrency: usize) -> Result<Vec<FileValidation>, Error> {
+
+    let folder = document_directory(&app)?;
+    if !folder.is_dir() { return Err(Error::new(ErrorKind::NotFound, format!("Directory not found: {:?}", folder))); }
+
+    let files = folder_files(folder, Some("csv"), &[], &[])?;
+
+    _validate_files(&files, concurrency, |file| validate_csv_file(app.clone(), file.to_string()))
+}
+
+/// 以固定數量的執行緒平行驗證檔案 (單一檔案失敗或 panic 只記錄在該檔案的結果中)
+/// ## 參數
+/// - `files`: 檔案名稱列表
+/// - `concurrency`: 同時驗證的檔案數上限 (至少 1)
+/// - `validate`: 驗證單一檔案的函式
+/// ## 返回
+/// - `Result<Vec<FileValidation>, Error>`: 每個檔案的驗證結果 (依檔名排序)
+fn _validate_files<F>(files: &[String], concurrency: usize, validate: F) -> Result<Vec<FileValidation>, Error> where F: Fn(&str) -> Result<Vec<String>, Error> + Sync {
+
+    let next = AtomicUsize::new(0);
+    let reports: Mutex<Vec<FileValidation>> = Mutex::new(Vec::with_capacity(files.len()));
+    let workers = concurrency.clamp(1, files.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some(file) = files.get(next.fetch_add(1, AtomicOrdering::Relaxed)) else { break };
+                let report = _validate_isolated(file, &validate);
+
+                if let Ok(mut reports) = reports.lock() { reports.push(report); }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().map_err(|error| Error::other(error.to_string()))?;
+    reports.sort_by(|report1, report2| report1.file.to_lowercase().cmp(&report2.file.to_lowercase()));
+
+    Ok(reports)
+}
+
+/// 找出完全沒有資料的欄位 (每一行都是空白，以原始字串讀取，任何欄位格式的檔案都可以使用)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 空白欄位的名稱 (依標題列順序)
+pub fn empty_csv_columns(app: AppHandle, filename: String, options: &ReadOptions) -> Result<Vec<String>, Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let (headers, records) = _read_raw_records(&resource_path, options)?;
+
+    let columns = headers.iter()
+        .enumerate()
+        .filter(|(index, _)| _is_empty_column(&records, *index))
+        .map(|(_, header)| header.to_string())
+        .collect();
+
+    Ok(columns)
+}
+
+/// 檢查資料列數是否在範圍內 (只計算列數，不解析欄位內容)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `min`: 最少列數 (含，可省略)
+/// - `max`: 最多列數 (含，可省略)
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<RowCountCheck, Error>`: 成功時返回是否通過、實際列數與說明
+pub fn check_csv_row_count(app: AppHandle, filename: String, min: Option<u64>, max: Option<u64>, options: &ReadOptions) -> Result<RowCountCheck, Error> {
+
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max { return Err(Error::new(ErrorKind::InvalidInput, format!("min {} is greater than max {}", min, max))); }
+    }
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let count = count_csv_rows(&resource_path, options)?;
+
+    let message = match (min, max) {
+        (Some(min), _) if count < min => format!("row count {} is below the minimum {}", count, min),
+        (_, Some(max)) if count > max => format!("row count {} is above the maximum {}", count, max),
+        _ => format!("row count {} is within the expected range", count),
+    };
+
+    let passed = min.is_none_or(|min| count >= min) && max.is_none_or(|max| count <= max);
+    Ok(RowCountCheck { passed, count, min, max, message })
+}
+
+/// 計算資料列數 (不含標題列，以 ByteRecord 逐行讀取，不解析欄位內容)
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<u64, Error>`: 成功時返回資料列數
+pub fn count_csv_rows(resource_path: &Path, options: &ReadOptions) -> Result<u64, Error> {
+
+    let mut reader = _open_raw_reader(resource_path, options)?;
+    let mut record = ByteRecord::new();
+    let mut count = 0;
+
+    loop {
+        match reader.read_byte_record(&mut record) {
+            Ok(true) => count += 1,
+            Ok(false) => break,
+            Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+        }
+    }
+
+    Ok(count)
+}
+
+/// 把含有經緯度欄位的 CSV 檔案轉成 GeoJSON FeatureCollection (每一行為一個 Point，其餘欄位放在 properties)
+/// - 經緯度空白、不是數字或超出範圍的行會被略過並回報
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// - `lat_col`: 緯度欄位名稱
+/// - `lng_col`: 經度欄位名稱
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<(Value, Vec<SkippedRow>), Error>`: 成功時返回 (FeatureCollection, 被略過的行)，找不到經緯度欄位時返回錯誤
+pub fn csv_to_geojson(app: AppHandle, filename: String, lat_col: String, lng_col: String, options: &ReadOptions) -> Result<(Value, Vec<SkippedRow>), Error> {
+
+    let resource_path = _csv_file_path(&app, filename)?;
+    let (headers, records) = _read_raw_records(&resource_path, options)?;
+
+    let column_index = |column: &str| headers.iter().position(|header| header.trim() == column.trim())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("Column '{}' not found in header", column)));
+
+    let lat_index = column_index(&lat_col)?;
+    let lng_index = column_index(&lng_col)?;
+
+    let mut features: Vec<Value> = Vec::new();
+    let mut skipped: Vec<SkippedRow> = Vec::new();
+
+    for record in records.iter() {
+
+        let line = record.position().map(|position| position.line()).unwrap_or_default();
+        let coordinate = |index: usize, column: &str, limit: f64| -> Result<f64, String> {
+            let text = record.get(index).unwrap_or_default().trim();
+            let value: f64 = text.parse().map_err(|_| format!("{} '{}' is not a number", column, text))?;
+            if !value.is_finite() || value.abs() > limit { return Err(format!("{} {} is out of range", column, value)); }
+            Ok(value)
+        };
+
+        let (lat, lng) = match (coordinate(lat_index, &lat_col, 90.0), coordinate(lng_index, &lng_col, 180.0)) {
+            (Ok(lat), Ok(lng)) => (lat, lng),
+            (Err(message), _) | (_, Err(message)) => { skipped.push(SkippedRow { line, row: None, message }); continue; }
+        };
+
+        let properties: Map<String, Value> = headers.iter()
+            .enumerate()
+            .filter(|(index, _)| *index != lat_index && *index != lng_index)
+            .map(|(index, header)| (header.to_string(), Value::String(record.get(index).unwrap_or_default().to_string())))
+            .collect();
+
+        features.push(serde_json::json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [lng, lat] },
+            "properties": properties,
+        }));
+    }
+
+    let collection = serde_json::json!({ "type": "FeatureCollection", "features": features });
+    Ok((collection, skipped))
+}
+
+/// 比較兩個資料夾內的檔案 (只在 A、只在 B、兩邊都有但內容不同)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `dir_a`: 資料夾 A (資源目錄內的資料夾名稱)
+/// - `dir_b`: 資料夾 B (資源目錄內的資料夾名稱)
+/// ## 返回
+/// - `Result<FolderDiff, Error>`: 成功時返回三個排序過的檔名列表，名稱不合法 (含 `..` 或完整路徑) 時返回 InvalidInput，資料夾不存在時返回 NotFound
+pub fn diff_folder_files(app: AppHandle, dir_a: String, dir_b: String) -> Result<FolderDiff, Error> {
+
+    let path_a = _resource_directory(&app, &dir_a)?;
+    let path_b = _resource_directory(&app, &dir_b)?;
+
+    let files_a = folder_files(path_a.clone(), None, &[], &[])?;
+    let files_b = folder_files(path_b.clone(), None, &[], &[])?;
+    let set_a: HashSet<&String> = files_a.iter().collect();
+    let set_b: HashSet<&String> = files_b.iter().collect();
+
+    let mut diff = FolderDiff { only_in_a: Vec::new(), only_in_b: Vec::new(), different: Vec::new() };
+
+    for name in files_a.iter() {
+        if !set_b.contains(name) { diff.only_in_a.push(name.clone()); continue; }
+        if file_checksum(&path_a.join(name))? != file_checksum(&path_b.join(name))? { diff.different.push(name.clone()); }
+    }
+
+    diff.only_in_b = files_b.iter().filter(|name| !set_a.contains(name)).cloned().collect();
+
+    Ok(diff)
+}
+
+/// 計算記錄內容的指紋 (以固定格式的資料列計算 SHA-256，與空白、欄位順序、記錄順序無關)
+/// - 資料相同但排版不同的檔案會得到相同的指紋，可用來判斷內容是否真的有變更
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<String, Error>`: 成功時返回十六進位的 SHA-256，失敗時返回錯誤
+pub fn records_fingerprint_hash(app: AppHandle, filename: String) -> Result<String, Error> {
+
+    let records = read_csv_file(app, filename, &ReadOptions::default())?;
+    let headers = CsvRecord::headers();
+    let rows = _canonical_rows(&records, &headers);
+
+    let mut hasher = Sha256::new();
+    serde_json::to_writer(&mut hasher, &(headers, rows)).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 計算檔案內容的 SHA-256 (十六進位小寫)
+/// ## 參數
+/// - `path`: 檔案的完整路徑
+/// ## 返回
+/// - `Result<String, Error>`: 成功時返回雜湊值
+pub fn file_checksum(path: &Path) -> Result<String, Error> {
+
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 讀取 config 資料夾內的檔案內容
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 檔案的名稱 (不可包含路徑)
+/// ## 返回
+/// - `Result<String, Error>`: 成功時返回檔案內容，檔名不合法時返回 InvalidInput
+pub fn read_config_file(app: &AppHandle, filename: String) -> Result<String, Error> {
+    let config_path = _config_file_path(app, filename)?;
+    read_to_string(config_path)
+}
+
+/// 讀取資源目錄根部的 app.json (資源資料夾的名稱)，應在初始化日誌系統之前呼叫並存入 managed state
+/// - 檔案不存在時使用預設值；格式錯誤或名稱是空字串時使用預設值，並把原因加到 `warnings` (此時日誌系統尚未初始化)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `warnings`: 等日誌系統初始化後才記錄的警告
+/// ## 返回
+/// - `AppConfig`: 資料夾設定
+pub fn load_app_config(app: &AppHandle, warnings: &mut Vec<String>) -> AppConfig {
+    match _read_app_config(app) {
+        Ok(config) => config,
+        Err(error) => { warnings.push(format!("Ignored app.json: {}", error)); AppConfig::default() }
+    }
+}
+
+/// 取得 CSV 檔案資料夾的完整路徑 (名稱依 app.json 的 documentDir，預設為 document)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回資料夾的完整路徑
+pub fn document_directory(app: &AppHandle) -> Result<PathBuf, Error> {
+    _configured_directory(app, &_app_config(app).document_dir)
+}
+
+/// 取得 JSON 設定檔資料夾的完整路徑 (名稱依 app.json 的 configDir，預設為 config)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回資料夾的完整路徑
+pub fn config_directory(app: &AppHandle) -> Result<PathBuf, Error> {
+    _configured_directory(app, &_app_config(app).config_dir)
+}
+
+/// 初始化日誌系統 (會在應用程序資源目錄中創建 logs 目錄，名稱依 app.json 的 logsDir)
+/// - 日誌等級取自 config/logging.json 的 level (沒有時為 debug)，之後可用 change_log_level 在執行時調整
+/// - 設定了環境變數 RUST_LOG 時依 env_logger 的語法解析 (例如: `csv_library_lib=debug,warn`)，logging.json 的 level 只作為預設等級；此時 change_log_level 只能進一步限制等級
+/// - 建立今天的日誌檔後，刪除超過保留天數 (config/logging.json 的 retentionDays，預設 30 天) 的舊日誌檔
+/// - logging.json 格式錯誤或等級不合法時改用預設值，並在日誌系統初始化後記錄警告
+/// ## 參數
+/// - `app`: Tauri 應用程式的 handle
+pub fn logger_setting(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+
+    let log_dir = _logs_directory(app.handle())?;
+    create_dir_all(&log_dir)?;
+
+    let today = Local::now().date_naive();
+    let log_file_name = format!("{}.log", today.format(LOG_FILE_DATE_FORMAT));
+    let log_file_path = log_dir.join(log_file_name);
+
+    let log_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_file_path)?;
+
+    let mut warnings: Vec<String> = Vec::new();
+    let config = _logging_config(app.handle(), &mut warnings);
+    let level = _initial_log_level(&config, &mut warnings);
+    let filters = std::env::var("RUST_LOG").ok().filter(|filters| !filters.trim().is_empty());
+
+    let mut builder = Builder::new();
+
+    match filters.as_deref() {
+        Some(filters) => builder.filter_level(level).parse_filters(filters),
+        None => builder.filter_level(LevelFilter::Trace),
+    };
+
+    builder
+        .format(|buffer, record| {
+            let mut style = buffer.style();
+            let level_color = match record.level() {
+                log::Level::Error => Color::Red,
+                log::Level::Warn => Color::Yellow,
+                log::Level::Info => Color::Green,
+                log::Level::Debug => Color::Blue,
+                log::Level::Trace => Color::Cyan,
+            };
+
+            writeln!(
+                buffer,
+                "{} [{}] {} - {}",
+                Local::now().format("%Y-%m-%d %H:%M:%S"),
+                style.set_color(level_color).value(record.level()),
+                record.target(),
+                record.args()
+            )
+        })
+        .target(env_logger::Target::Pipe(Box::new(log_file)))
+        .init();
+
+    if filters.is_none() { log::set_max_level(level); }
+    ww_print!(format!("Log file location: {:?}", log_file_path));
+
+    for warning in warnings.iter() { warn!("{}", warning); }
+
+    let retention_days = config.retention_days.unwrap_or(DEFAULT_LOG_RETENTION_DAYS);
+    if retention_days > 0 {
+        let removed = prune_log_files(&log_dir, today, retention_days);
+        if !removed.is_empty() { info!("Removed {} expired log files: {:?}", removed.len(), removed); }
+    }
+
+    Ok(())
+}
+
+/// 刪除超過保留天數的日誌檔 (只處理檔名為「YYYYMMDD.log」的檔案，其他檔案不受影響)
+/// ## 參數
+/// - `log_dir`: logs 資料夾路徑
+/// - `today`: 今天的日期
+/// - `retention_days`: 保留最近幾天 (今天算第 1 天)
+/// ## 返回
+/// - `Vec<String>`: 被刪除的檔名 (無法刪除的檔案只記錄警告)
+pub fn prune_log_files(log_dir: &Path, today: NaiveDate, retention_days: u32) -> Vec<String> {
+
+    let entries = match read_dir(log_dir) {
+        Ok(entries) => entries,
+        Err(error) => { warn!("Failed to read log folder {:?}: {}", log_dir, error); return vec![]; }
+    };
+
+    let mut removed = vec![];
+
+    for entry in entries.flatten() {
+
+        let path = entry.path();
+        if !path.is_file() || !path.extension().is_some_and(|extension| extension == "log") { continue; }
+
+        let date = match path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| NaiveDate::parse_from_str(stem, LOG_FILE_DATE_FORMAT).ok()) {
+            Some(date) => date,
+            None => continue,
+        };
+
+        if (today - date).num_days() < i64::from(retention_days) { continue; }
+
+        match fs::remove_file(&path) {
+            Ok(()) => removed.push(entry.file_name().to_string_lossy().to_string()),
+            Err(error) => warn!("Failed to remove log file {:?}: {}", path, error),
+        }
+    }
+
+    removed.sort();
+    removed
+}
+
+/// 在執行時調整日誌等級 (不需要重新啟動)
+/// ## 參數
+/// - `level`: 日誌等級 (off / error / warn / info / debug / trace，不分大小寫)
+/// ## 返回
+/// - `Result<LevelFilter, Error>`: 成功時返回新的日誌等級，等級名稱不合法時返回 InvalidInput
+pub fn change_log_level(level: &str) -> Result<LevelFilter, Error> {
+
+    let level = _parse_log_level(level)?;
+
+    log::set_max_level(level);
+    info!("Log level set to {}", level);
+
+    Ok(level)
+}
+
+/// 決定啟動時的日誌等級 (config/logging.json > debug)
+/// - 設定不合法時改用 debug，並把原因加到 `warnings` (此時日誌系統尚未初始化)
+/// ## 參數
+/// - `config`: logging.json 的設定
+/// - `warnings`: 等日誌系統初始化後才記錄的警告
+/// ## 返回
+/// - `LevelFilter`: 日誌等級
+fn _initial_log_level(config: &LoggingConfig, warnings: &mut Vec<String>) -> LevelFilter {
+
+    if let Some(level) = config.level.as_deref() {
+        match _parse_log_level(level) {
+            Ok(level) => return level,
+            Err(error) => warnings.push(format!("Ignored logging.json: {}", error)),
+        }
+    }
+
+    LevelFilter::Debug
+}
+
+/// 讀取 config 資料夾內的 logging.json (檔案不存在或格式錯誤時使用預設值，格式錯誤的原因加到 `warnings`)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `warnings`: 等日誌系統初始化後才記錄的警告
+/// ## 返回
+/// - `LoggingConfig`: 日誌設定
+fn _logging_config(app: &AppHandle, warnings: &mut Vec<String>) -> LoggingConfig {
+    match _read_logging_config(app) {
+        Ok(config) => config.unwrap_or_default(),
+        Err(error) => { warnings.push(format!("Ignored logging.json: {}", error)); LoggingConfig::default() }
+    }
+}
+
+/// 讀取 config 資料夾內的 logging.json
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `Result<Option<LoggingConfig>, Error>`: 檔案不存在時返回 None，JSON 格式錯誤時返回錯誤
+fn _read_logging_config(app: &AppHandle) -> Result<Option<LoggingConfig>, Error> {
+
+    let config_path = _config_file_path(app, String::from("logging.json"))?;
+    if !config_path.exists() { return Ok(None); }
+
+    let content = read_to_string(&config_path)?;
+    let config: LoggingConfig = serde_json::from_str(&content)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid logging file {:?}: {}", config_path, error)))?;
+
+    Ok(Some(config))
+}
+
+/// 解析日誌等級名稱
+/// ## 參數
+/// - `level`: 日誌等級 (off / error / warn / info / debug / trace，不分大小寫)
+/// ## 返回
+/// - `Result<LevelFilter, Error>`: 等級名稱不合法時返回 InvalidInput
+fn _parse_log_level(level: &str) -> Result<LevelFilter, Error> {
+    LevelFilter::from_str(level.trim())
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Invalid log level '{}', expected one of: off, error, warn, info, debug, trace", level)))
+}
+
+/// 取得 CSV 檔案的完整路徑
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: CSV 檔案的名稱
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回檔案的完整路徑，失敗時返回錯誤
+fn _csv_file_path(app: &AppHandle, filename: String) -> Result<PathBuf, Error> {
+    if filename.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Filename cannot be empty",
+        ));
+    }
+
+    let resource_path = document_directory(app)?;
+    _contained_path(&resource_path, &filename)
+}
+
+/// 取得檔案的變更記錄路徑 (<logs 資料夾>/changes/<檔名>.jsonl)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 檔案的名稱
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回變更記錄的完整路徑，檔名不合法 (含路徑分隔符號或 `..`) 時返回 InvalidInput
+fn _change_log_path(app: &AppHandle, filename: &str) -> Result<PathBuf, Error> {
+    let log_dir = _logs_directory(app)?;
+    _change_log_file(&log_dir.join("changes"), filename)
+}
+
+/// 取得 changes 資料夾內的變更記錄路徑 (資料夾不存在時會建立)
+/// ## 參數
+/// - `changes_dir`: changes 資料夾路徑
+/// - `filename`: 檔案的名稱 (不可包含路徑分隔符號或 `..`)
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回變更記錄的完整路徑，檔名不合法或跳出資料夾時返回 InvalidInput
+fn _change_log_file(changes_dir: &Path, filename: &str) -> Result<PathBuf, Error> {
+    if filename.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Filename cannot be empty",
+        ));
+    }
+
+    create_dir_all(changes_dir)?;
+    _contained_path(changes_dir, filename)?;
+    _contained_path(changes_dir, &format!("{}.jsonl", filename))
+}
+
+/// 取得日誌檔資料夾的完整路徑 (名稱依 app.json 的 logsDir，預設為 logs)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回資料夾的完整路徑
+fn _logs_directory(app: &AppHandle) -> Result<PathBuf, Error> {
+    _configured_directory(app, &_app_config(app).logs_dir)
+}
+
+/// 取得資源目錄內設定的資料夾路徑 (資料夾不存在時不會返回錯誤)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `directory`: 資料夾名稱
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回資料夾的完整路徑
+fn _configured_directory(app: &AppHandle, directory: &str) -> Result<PathBuf, Error> {
+    app.path().resolve(directory, BaseDirectory::Resource).map_err(|error| Error::new(ErrorKind::NotFound, error.to_string()))
+}
+
+/// 取得 managed state 中的資料夾設定 (尚未載入時使用預設值)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `AppConfig`: 資料夾設定
+fn _app_config(app: &AppHandle) -> AppConfig {
+    app.try_state::<AppConfig>().map(|config| config.inner().clone()).unwrap_or_default()
+}
+
+/// 讀取並檢查資源目錄根部的 app.json
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// ## 返回
+/// - `Result<AppConfig, Error>`: 檔案不存在時返回預設值，JSON 格式錯誤或名稱是空字串時返回錯誤
+fn _read_app_config(app: &AppHandle) -> Result<AppConfig, Error> {
+
+    let config_path = app.path().resolve("app.json", BaseDirectory::Resource).map_err(|error| Error::new(ErrorKind::NotFound, error.to_string()))?;
+    if !config_path.exists() { return Ok(AppConfig::default()); }
+
+    let content = read_to_string(&config_path)?;
+    let config: AppConfig = serde_json::from_str(&content)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid app file {:?}: {}", config_path, error)))?;
+
+    for directory in [&config.document_dir, &config.config_dir, &config.logs_dir] {
+        if directory.trim().is_empty() { return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid app file {:?}: directory names cannot be empty", config_path))); }
+    }
+
+    Ok(config)
+}
+
+/// 取得資源目錄內的資料夾路徑 (只接受單一層的資料夾名稱，不接受 `..` 或完整路徑)，資料夾不存在時返回錯誤
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `directory`: 資料夾名稱
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回資料夾的完整路徑，名稱不合法或跳出資源目錄時返回 InvalidInput
+fn _resource_directory(app: &AppHandle, directory: &str) -> Result<PathBuf, Error> {
+    if directory.trim().is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Directory cannot be empty",
+        ));
+    }
+
+    let resource_dir = app.path().resource_dir().map_err(|error| Error::new(ErrorKind::NotFound, error.to_string()))?;
+    let path = _contained_path(&resource_dir, directory)?;
+
+    if !path.is_dir() { return Err(Error::new(ErrorKind::NotFound, format!("Directory not found: {:?}", path))); }
+    Ok(path)
+}
+
+/// 取得 config 資料夾內檔案的完整路徑
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `filename`: 檔案的名稱
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回檔案的完整路徑，失敗時返回錯誤
+fn _config_file_path(app: &AppHandle, filename: String) -> Result<PathBuf, Error> {
+    if filename.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Filename cannot be empty",
+        ));
+    }
+
+    let config_path = config_directory(app)?;
+    _contained_path(&config_path, &filename)
+}
+
+/// 把檔名接在資料夾之後，並確認結果仍在資料夾內 (防止 `../`、絕對路徑或符號連結跳出資料夾)
+/// ## 參數
+/// - `directory`: 資料夾路徑
+/// - `filename`: 檔案名稱 (不可包含路徑分隔符號或 `..`)
+/// ## 返回
+/// - `Result<PathBuf, Error>`: 成功時返回檔案的完整路徑，檔名不合法或跳出資料夾時返回 InvalidInput
+fn _contained_path(directory: &Path, filename: &str) -> Result<PathBuf, Error> {
+
+    let invalid = || Error::new(ErrorKind::InvalidInput, format!("Invalid filename: {:?}", filename));
+
+    let mut components = Path::new(filename).components();
+    let is_plain_name = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+    if !is_plain_name || filename.contains(['/', '\\']) { return Err(invalid()); }
+
+    let path = directory.join(filename);
+    let directory = directory.canonicalize()?;
+    let resolved = if path.exists() { path.canonicalize()? } else { directory.join(filename) };
+
+    if !resolved.starts_with(&directory) { return Err(invalid()); }
+    Ok(path)
+}
+
+/// 以指定的文字編碼開啟 CSV 檔案，邊讀取邊轉成 UTF-8 並移除開頭的 BOM (Excel 匯出的檔案常帶有 BOM，會讓第一個欄位名稱對不上)
+/// - UTF-8 時直接串流讀取檔案，其他編碼時以 DecodingReader 逐段轉換 (都不會把整個檔案讀進記憶體)
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項 (使用 encoding)
+/// ## 返回
+/// - `Result<Box<dyn Read>, CsvError>`: 成功時返回 UTF-8 內容的 Reader，編碼名稱不支援時返回錯誤；內容無法解碼時讀取過程會返回帶有行號的錯誤 (不會產生亂碼)
+fn _decoded_csv_source(resource_path: &Path, options: &ReadOptions) -> Result<Box<dyn Read>, CsvError> {
+
+    let encoding = match options.encoding.as_deref().map(str::trim) {
+        None | Some("") => UTF_8,
+        Some(label) => Encoding::for_label(label.as_bytes()).ok_or_else(|| CsvError::InvalidInput { message: format!("Unsupported encoding: {}", label) })?,
+    };
+
+    let file = File::open(resource_path)?;
+    if encoding != UTF_8 { return Ok(Box::new(DecodingReader::new(file, encoding))); }
+
+    let mut reader = BufReader::new(file);
+    if reader.fill_buf()?.starts_with(UTF8_BOM) { reader.consume(UTF8_BOM.len()); }
+
+    Ok(Box::new(reader))
+}
+
+/// 檔名的副檔名是否相同 (不分大小寫)
+/// ## 參數
+/// - `name`: 檔名
+/// - `extension`: 副檔名 (不含「.」)
+/// ## 返回
+/// - `bool`: 是否相同
+fn _has_extension(name: &str, extension: &str) -> bool {
+    Path::new(name).extension().and_then(|value| value.to_str()).is_some_and(|value| value.eq_ignore_ascii_case(extension))
+}
+
+/// 開啟不對應到 CsvRecord 的原始 CSV 讀取器 (容許各行欄位數不同，內容已轉成 UTF-8 並移除 BOM)
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<Reader<Box<dyn Read>>, Error>`: 成功時返回讀取器，分隔符號或編碼名稱不合法時返回 InvalidInput
+fn _open_raw_reader(resource_path: &Path, options: &ReadOptions) -> Result<Reader<Box<dyn Read>>, Error> {
+    Ok(ReaderBuilder::new()
+        .flexible(true)
+        .delimiter(_delimiter_byte(options)?)
+        .from_reader(_decoded_csv_source(resource_path, options)?))
+}
+
+/// 讀取原始的標題列與全部資料列 (不對應到 CsvRecord)
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項 (使用 delimiter / encoding)
+/// ## 返回
+/// - `Result<(StringRecord, Vec<StringRecord>), Error>`: 成功時返回 (標題列, 資料列)
+fn _read_raw_records(resource_path: &Path, options: &ReadOptions) -> Result<(StringRecord, Vec<StringRecord>), Error> {
+
+    let mut reader = _open_raw_reader(resource_path, options)?;
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+    };
+
+    let mut records: Vec<StringRecord> = Vec::new();
+
+    for result in reader.records() {
+        match result {
+            Ok(record) => records.push(record),
+            Err(error) => return Err(Error::new(ErrorKind::InvalidData, error.to_string())),
+        }
+    }
+
+    Ok((headers, records))
+}
+
+/// 以 StringRecord 索引只讀取指定的欄位 (輸出的欄位名稱與 CsvRecord 序列化後相同)
+/// ## 參數
+/// - `resource_path`: CSV 檔案路徑
+/// - `options`: 讀取選項 (`columns` 為要讀取的欄位)
+/// ## 返回
+/// - `Result<Vec<Value>, CsvError>`: 成功時返回只含指定欄位的物件，解析失敗時返回帶有行號 / 第幾筆資料的 CsvError::Parse
+fn _read_csv_columns(resource_path: &Path, options: &ReadOptions) -> Result<Vec<Value>, CsvError> {
+
+    for column in options.columns.iter() { _check_column(column)?; }
+
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .delimiter(_delimiter_byte(options)?)
+        .from_reader(_decoded_csv_source(resource_path, options)?);
+    let mut headers = reader.headers().map_err(|error| _csv_parse_error(error, None, None))?.clone();
+    _resolve_duplicate_headers(&mut headers, options.duplicate_headers)?;
+
+    let index_of = |column: &str| headers.iter().position(|header| header.trim().eq_ignore_ascii_case(column));
+
+    let mut selected: Vec<(String, Option<usize>)> = vec![];
+    for name in CsvRecord::headers() {
+        if !options.columns.iter().any(|column| column.trim().eq_ignore_ascii_case(&name)) { continue; }
+
+        let index = index_of(&name);
+        let optional = name == "Example" || CsvRecord::MULTI_VALUE_COLUMNS.contains(&name.as_str());
+        if index.is_none() && !optional { return Err(CsvError::Parse { line: Some(1), row: None, field: None, message: format!("Missing column: {}", name) }); }
+
+        selected.push((name, index));
+    }
+
+    let name_index = index_of("Name");
+
+    let mut rows: Vec<(String, Map<String, Value>)> = vec![];
+    let mut record = StringRecord::new();
+
+    while reader.read_record(&mut record).map_err(|error| _csv_parse_error(error, None, Some(&headers)))? {
+        let line = record.position().map_or(0, |position| position.line());
+        let row = record.position().map(|position| position.record()).filter(|record| *record > 0);
+        let mut object = Map::new();
+
+        for (name, index) in selected.iter() {
+            let field = index.and_then(|index| record.get(index)).unwrap_or_default();
+            if let Some(value) = _column_value(name, field, options, line, row)? { object.insert(name.clone(), value); }
+        }
+
+        let name = name_index.and_then(|index| record.get(index)).unwrap_or_default().trim().to_string();
+        rows.push((name, object));
+    }
+
+    if options.sort_by_name {
+        let compare = name_comparator(options.locale.as_deref())?;
+        rows.sort_by(|(name1, _), (name2, _)| compare(name1, name2));
+    }
+    if options.reverse { rows.reverse(); }
+
+    Ok(rows.into_iter().map(|(_, object)| Value::Object(object)).collect())
+}
+
+/// 把單一儲存格轉換成與 CsvRecord 序列化後相同的 JSON 值 (空白的多值欄位與 Example 不輸出)
+/// ## 參數
+/// - `name`: 欄位名稱 (CsvRecord 的欄位名稱)
+/// - `field`: 儲存格的文字
+/// - `options`: 讀取選項
+/// - `line`: 所在行號 (錯誤訊息用)
+/// - `row`: 第幾筆資料 (錯誤訊息用)
+/// ## 返回
+/// - `Result<Option<Value>, CsvError>`: 成功時返回 JSON 值 (不輸出時為 None)，Level 不是數字時返回 CsvError::Parse
+fn _column_value(name: &str, field: &str, options: &ReadOptions, line: u64, row: Option<u64>) -> Result<Option<Value>, CsvError> {
+
+    if CsvRecord::MULTI_VALUE_COLUMNS.contains(&name) {
+        let values = options.multi_values(field);
+        if values.is_empty() { return Ok(None); }
+
+        let value = match options.join_multivalue.as_deref() {
+            Some(separator) => Value::from(values.join(separator)),
+            None => Value::from(values),
+        };
+        return Ok(Some(value));
+    }
+
+    match name {
+        "Level" => match field.trim().parse::<u8>() {
+            Ok(level) => Ok(Some(Value::from(level))),
+            Err(error) => Err(CsvError::Parse { line: Some(line), row, field: Some(String::from("Level")), message: format!("invalid Level {:?}: {}", field, error) }),
+        },
+        "Example" if field.is_empty() => Ok(None),
+        "Notes" => Ok(Some(_split_notes(field, options).unwrap_or_else(|| Value::from(field)))),
+        _ => Ok(Some(Value::from(field))),
+    }
+}
+
+/// 檢查某個欄位是否在每一行都是空白 (欄位數不足的行視為空白)
+/// ## 參數
+/// - `records`: 原始資料列
+/// - `index`: 欄位位置
+/// ## 返回
+/// - `bool`: 全部空白時返回 true
+fn _is_empty_column(records: &[StringRecord], index: usize) -> bool {
+    records.iter().all(|record| record.get(index).unwrap_or_default().trim().is_empty())
+}
+
+/// 解析 CSV 檔案並返回記錄
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項
+/// - `notes`: 解析過程中的附帶資訊
+/// ## 返回
+/// - `Result<Vec<T>, CsvError>`: 成功時返回記錄的向量
+fn _parse_csv_file<T>(resource_path: String, options: &ReadOptions, notes: &mut ParseNotes) -> Result<Vec<T>, CsvError> where T: DeserializeOwned + Debug {
+    let records = _parse_csv_file_with_lines(resource_path, options, notes)?;
+    Ok(records.into_iter().map(|(_, record)| record).collect())
+}
+
+/// 解析 CSV 檔案並返回記錄與其起始行號
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項
+/// - `notes`: 解析過程中的附帶資訊
+/// ## 返回
+/// - `Result<Vec<(u64, T)>, CsvError>`: 成功時返回 (行號, 記錄) 的向量
+fn _parse_csv_file_with_lines<T>(resource_path: String, options: &ReadOptions, notes: &mut ParseNotes) -> Result<Vec<(u64, T)>, CsvError> where T: DeserializeOwned + Debug {
+
+    let mut records: Vec<(u64, T)> = Vec::new();
+
+    _for_each_csv_record(resource_path, options, notes, |line, record| {
+        records.push((line, record));
+        Ok(())
+    })?;
+
+    Ok(records)
+}
+
+/// 逐筆解析 CSV 檔案，每解析一筆就交給 `action` 處理 (不會保留全部記錄)
+/// - 預設容許每一行多出一個空白的結尾欄位 (試算表匯出常見的多餘逗號)，並記錄一筆警告
+/// - 有設定 `multi_value_separator` 時，多值欄位會先改寫成以逗號分隔 (值本身的逗號寫成「\,」)，再交給 CsvRecord 反序列化
+/// - 有設定 `parenthesized_negatives` 時，數值欄位中「(5)」這類會計格式的數字會先轉成「-5」
+/// - 有設定 `level_rounding` 時，小數格式的 Level (例如: "3.7") 會先轉成整數，並記錄在 `notes`
+/// - 有設定 `lenient` 時，無法解析的資料列會被略過並記錄在 `notes.skipped`，而不是返回錯誤
+/// ## 參數
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項
+/// - `notes`: 解析過程中的附帶資訊
+/// - `action`: 處理單筆 (行號, 記錄) 的函式，返回錯誤時會中止解析
+/// ## 返回
+/// - `Result<usize, CsvError>`: 成功時返回解析的記錄數，解析失敗時返回帶有行號的 CsvError::Parse
+fn _for_each_csv_record<T, F>(resource_path: String, options: &ReadOptions, notes: &mut ParseNotes, mut action: F) -> Result<usize, CsvError> where T: DeserializeOwned + Debug, F: FnMut(u64, T) -> Result<(), Error> {
+    if resource_path.is_empty() {
+        return Err(CsvError::InvalidInput { message: String::from("Resource path cannot be empty") });
+    }
+
+    let source = _decoded_csv_source(Path::new(&resource_path), options)?;
+    let mut reader = ReaderBuilder::new()
+        .flexible(!options.strict_trailing_field)
+        .delimiter(_delimiter_byte(options)?)
+        .from_reader(source);
+
+    let mut headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(error) => return Err(_csv_parse_error(error, None, None)),
+    };
+
+    if !options.strict_trailing_field && headers.len() > 1 && headers.get(headers.len() - 1) == Some("") {
+        headers.truncate(headers.len() - 1);
+    }
+
+    _resolve_duplicate_headers(&mut headers, options.duplicate_headers)?;
+
+    let level_index = headers.iter().position(|header| header == "Level");
+    let numeric_indexes = _numeric_column_indexes(&headers, options);
+    let multi_value_indexes: Vec<usize> = headers.iter().enumerate().filter(|(_, header)| CsvRecord::MULTI_VALUE_COLUMNS.contains(header)).map(|(index, _)| index).collect();
+    let mut count = 0;
+    let mut trimmed_count = 0;
+    let mut record = StringRecord::new();
+
+    loop {
+
+        match reader.read_record(&mut record) {
+            Ok(true) => {},
+            Ok(false) => break,
+            Err(error) if options.lenient && matches!(error.kind(), csv::ErrorKind::UnequalLengths { .. }) => {
+                let error = _csv_parse_error(error, None, None);
+                if let CsvError::Parse { line, row, .. } = &error { notes.skipped.push(SkippedRow { line: line.unwrap_or_default(), row: *row, message: error.to_string() }); }
+                continue;
+            }
+            Err(error) => return Err(_csv_parse_error(error, None, None)),
+        }
+
+        let line = record.position().map(|position| position.line()).unwrap_or_default();
+        let row = record.position().map(|position| position.record()).filter(|record| *record > 0);
+
+        if !options.strict_trailing_field {
+            match _trim_trailing_field(&mut record, headers.len(), line, row) {
+                Ok(trimmed) => if trimmed { trimmed_count += 1; },
+                Err(error) if options.lenient => { notes.skipped.push(SkippedRow { line, row, message: error.to_string() }); continue; },
+                Err(error) => return Err(error),
+            }
+        }
+
+        if options.multi_value_separator.as_deref().is_some_and(|separator| !separator.is_empty()) {
+            _replace_fields(&mut record, |position, field| multi_value_indexes.contains(&position).then(|| join_multi_value(&options.multi_values(field))));
+        }
+
+        if options.parenthesized_negatives {
+            match _convert_parenthesized_negatives(&mut record, &numeric_indexes, &headers, line, row) {
+                Ok(()) => {},
+                Err(error) if options.lenient => { notes.skipped.push(SkippedRow { line, row, message: error.to_string() }); continue; },
+                Err(error) => return Err(error),
+            }
+        }
+
+        if let (Some(rounding), Some(index)) = (options.level_rounding, level_index) {
+            if let Some(coerced) = _coerce_level(&mut record, index, rounding, line) { notes.coerced.push(coerced); }
+        }
+
+        match record.deserialize(Some(&headers)) {
+            Ok(value) => action(line, value)?,
+            Err(error) if options.lenient => {
+                let error = _csv_parse_error(error, Some(line), Some(&headers));
+                notes.skipped.push(SkippedRow { line, row, message: error.to_string() });
+                continue;
+            }
+            Err(error) => return Err(_csv_parse_error(error, Some(line), Some(&headers))),
+        }
+
+        count += 1;
+    }
+
+    if trimmed_count > 0 { warn!("Ignored an empty trailing field on {} rows: {}", trimmed_count, resource_path); }
+    if !notes.skipped.is_empty() { warn!("Skipped {} rows that could not be parsed: {}", notes.skipped.len(), resource_path); }
+
+    Ok(count)
+}
+
+/// 把 csv crate 的錯誤轉換成帶有行號、第幾筆資料與欄位名稱的 CsvError::Parse
+/// - 例如: "row 3182: field 'Level': invalid digit found in string"
+/// ## 參數
+/// - `error`: csv crate 的錯誤
+/// - `line`: 錯誤本身沒有位置資訊時使用的行號
+/// - `headers`: 標題列 (用來把欄位位置轉換成欄位名稱)
+/// ## 返回
+/// - `CsvError`: 解析錯誤 (I/O 錯誤時為 CsvError::Io)
+fn _csv_parse_error(error: csv::Error, line: Option<u64>, headers: Option<&StringRecord>) -> CsvError {
+
+    let line = error.position().map(|position| position.line()).or(line);
+    let row = error.position().map(|position| position.record()).filter(|record| *record > 0);
+    let message = error.to_string();
+
+    match error.into_kind() {
+        csv::ErrorKind::Io(error) => CsvError::from(error),
+        csv::ErrorKind::Deserialize { err, .. } => {
+            let field = err.field().map(|index| {
+                headers.and_then(|headers| headers.get(index as usize)).map(str::to_string).unwrap_or_else(|| format!("#{}", index + 1))
+            });
+            CsvError::Parse { line, row, field, message: err.kind().to_string() }
+        }
+        _ => CsvError::Parse { line, row, field: None, message },
+    }
+}
+
+/// 把小數格式的 Level 依捨入方式轉成整數 (本來就是整數或無法轉換時不處理，交給後續解析)
+/// ## 參數
+/// - `record`: 單筆原始記錄
+/// - `index`: Level 欄位的位置
+/// - `rounding`: 捨入方式
+/// - `line`: 記錄所在的行號
+/// ## 返回
+/// - `Option<CoercedLevel>`: 有轉換時返回轉換資訊
+fn _coerce_level(record: &mut StringRecord, index: usize, rounding: LevelRounding, line: u64) -> Option<CoercedLevel> {
+
+    let original = record.get(index)?.trim().to_string();
+    if original.parse::<u8>().is_ok() { return None; }
+
+    let number = original.parse::<f64>().ok()?;
+    let rounded = match rounding {
+        LevelRounding::Floor => number.floor(),
+        LevelRounding::Ceil => number.ceil(),
+        LevelRounding::Nearest => number.round(),
+    };
+
+    if !(0.0..=u8::MAX as f64).contains(&rounded) { return None; }
+
+    let level = rounded as u8;
+    _replace_fields(record, |position, _| if position == index { Some(level.to_string()) } else { None });
+
+    Some(CoercedLevel { line, original, level })
+}
+
+/// 套用 parenthesizedNegatives 的欄位位置 (numericColumns 為空時只有 Level)
+/// ## 參數
+/// - `headers`: 標題列
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Vec<usize>`: 數值欄位的位置
+fn _numeric_column_indexes(headers: &StringRecord, options: &ReadOptions) -> Vec<usize> {
+
+    let is_numeric = |header: &str| {
+        if options.numeric_columns.is_empty() { return ReadOptions::DEFAULT_NUMERIC_COLUMNS.iter().any(|column| column.eq_ignore_ascii_case(header)); }
+        options.numeric_columns.iter().any(|column| column.trim().eq_ignore_ascii_case(header))
+    };
+
+    headers.iter().enumerate()
+        .filter(|(_, header)| is_numeric(header))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// 把數值欄位中括號包住的數字轉成負數 (會計格式)，只處理整個欄位都是「(...)」的情況，其他欄位不處理
+/// - 例如: "(5)" => "-5"、"( 1.5 )" => "-1.5"、"(1,234.50)" => "-1234.50"
+/// ## 參數
+/// - `record`: 單筆原始記錄
+/// - `numeric_indexes`: 數值欄位的位置
+/// - `headers`: 標題列 (錯誤訊息中的欄位名稱)
+/// - `line`: 記錄所在的行號
+/// - `row`: 第幾筆資料
+/// ## 返回
+/// - `Result<(), CsvError>`: 數值欄位的括號內不是數字時 (例如: "(abc)") 返回 CsvError::Parse
+fn _convert_parenthesized_negatives(record: &mut StringRecord, numeric_indexes: &[usize], headers: &StringRecord, line: u64, row: Option<u64>) -> Result<(), CsvError> {
+
+    for &index in numeric_indexes {
+
+        let Some(field) = record.get(index) else { continue };
+        let Some(inner) = field.trim().strip_prefix('(').and_then(|field| field.strip_suffix(')')) else { continue };
+
+        let number = inner.trim().replace(',', "");
+        if number.is_empty() || number.starts_with('-') || number.parse::<f64>().is_err() {
+            return Err(CsvError::Parse { line: Some(line), row, field: headers.get(index).map(str::to_string), message: format!("invalid parenthesized number '{}'", field) });
+        }
+    }
+
+    _replace_fields(record, |position, field| {
+        if !numeric_indexes.contains(&position) { return None; }
+        let inner = field.trim().strip_prefix('(')?.strip_suffix(')')?;
+        Some(format!("-{}", inner.trim().replace(',', "")))
+    });
+
+    Ok(())
+}
+
+/// 依 `replace` 的結果改寫記錄中的欄位 (返回 None 的欄位保持原樣，會保留記錄的位置資訊)
+/// ## 參數
+/// - `record`: 單筆原始記錄
+/// - `replace`: (欄位位置, 欄位值) => 新的欄位值
+fn _replace_fields<F>(record: &mut StringRecord, replace: F) where F: Fn(usize, &str) -> Option<String> {
+
+    let replacements: Vec<Option<String>> = record.iter().enumerate().map(|(position, field)| replace(position, field)).collect();
+    if replacements.iter().all(|replacement| replacement.is_none()) { return; }
+
+    let mut replaced = StringRecord::new();
+
+    for (field, replacement) in record.iter().zip(replacements.iter()) {
+        replaced.push_field(replacement.as_deref().unwrap_or(field));
+    }
+
+    replaced.set_position(record.position().cloned());
+    *record = replaced;
+}
+
+/// 找出重複的欄位名稱 (去除前後空白後比較，空白的欄位名稱不計算)
+/// ## 參數
+/// - `headers`: 標題列
+/// ## 返回
+/// - `Vec<String>`: 重複的欄位名稱 (每個名稱只出現一次)
+fn _duplicate_headers(headers: &StringRecord) -> Vec<String> {
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut duplicates: Vec<String> = vec![];
+
+    for header in headers.iter().map(str::trim).filter(|header| !header.is_empty()) {
+        if !seen.insert(header) && !duplicates.iter().any(|duplicate| duplicate == header) { duplicates.push(header.to_string()); }
+    }
+
+    duplicates
+}
+
+/// 依設定處理標題列中重複的欄位名稱
+/// - Error: 返回 InvalidData 並列出重複的名稱
+/// - Rename: 第二個之後的欄位改名成「名稱_2」、「名稱_3」… (避開已存在的名稱)
+/// ## 參數
+/// - `headers`: 標題列 (Rename 時會被修改)
+/// - `handling`: 處理方式
+/// ## 返回
+/// - `Result<(), Error>`: Error 模式且有重複時返回錯誤
+fn _resolve_duplicate_headers(headers: &mut StringRecord, handling: DuplicateHeaders) -> Result<(), Error> {
+
+    let duplicates = _duplicate_headers(headers);
+    if duplicates.is_empty() { return Ok(()); }
+
+    if handling == DuplicateHeaders::Error {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Duplicate header names: {}", duplicates.join(", "))));
+    }
+
+    let mut used: HashSet<String> = headers.iter().map(|header| header.trim().to_string()).collect();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut renamed = StringRecord::new();
+
+    for header in headers.iter() {
+        let name = header.trim();
+
+        if name.is_empty() || seen.insert(name.to_string()) { renamed.push_field(header); continue; }
+
+        let mut number = 2;
+        while used.contains(&format!("{}_{}", name, number)) { number += 1; }
+
+        let unique = format!("{}_{}", name, number);
+        warn!("Renamed duplicate header '{}' to '{}'", name, unique);
+        used.insert(unique.clone());
+        renamed.push_field(&unique);
+    }
+
+    *headers = renamed;
+    Ok(())
+}
+
+/// 驗證單一檔案，並把錯誤與 panic 記錄在結果中 (不會往外傳)
+/// ## 參數
+/// - `file`: CSV 檔案的名稱
+/// - `validate`: 驗證單一檔案的函式
+/// ## 返回
+/// - `FileValidation`: 驗證結果
+fn _validate_isolated<F>(file: &str, validate: &F) -> FileValidation where F: Fn(&str) -> Result<Vec<String>, Error> {
+
+    let (problems, error) = match catch_unwind(AssertUnwindSafe(|| validate(file))) {
+        Ok(Ok(problems)) => (problems, None),
+        Ok(Err(error)) => (vec![], Some(error.to_string())),
+        Err(panic) => {
+            let message = panic.downcast_ref::<&str>().map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic"));
+            (vec![], Some(format!("Validation panicked: {}", message)))
+        }
+    };
+
+    if let Some(error) = &error { warn!("Failed to validate {}: {}", file, error); }
+    FileValidation { file: file.to_string(), problems, error }
+}
+
+/// 取得讀取選項中的分隔符號 (未指定時為逗號)
+/// ## 參數
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<u8, Error>`: 分隔符號不是單一位元組時返回 InvalidInput
+fn _delimiter_byte(options: &ReadOptions) -> Result<u8, Error> {
+
+    let delimiter = match options.delimiter.as_deref() {
+        Some(delimiter) => delimiter,
+        None => return Ok(b','),
+    };
+
+    match delimiter.as_bytes() {
+        [byte] => Ok(*byte),
+        _ => Err(Error::new(ErrorKind::InvalidInput, format!("Delimiter must be a single byte, got {:?}", delimiter))),
+    }
+}
+
+/// 移除多出來的空白結尾欄位 (只容許多一個且必須是空白，其餘欄位數不符的情況視為錯誤)
+/// ## 參數
+/// - `record`: 單筆原始記錄
+/// - `width`: 標題列的欄位數
+/// - `line`: 記錄所在的行號 (錯誤訊息用)
+/// - `row`: 第幾筆資料 (錯誤訊息用)
+/// ## 返回
+/// - `Result<bool, CsvError>`: 有移除欄位時返回 true，欄位數不符時返回帶有行號與第幾筆資料的 CsvError::Parse
+fn _trim_trailing_field(record: &mut StringRecord, width: usize, line: u64, row: Option<u64>) -> Result<bool, CsvError> {
+
+    if record.len() == width { return Ok(false); }
+
+    if record.len() == width + 1 && record.get(width).is_some_and(|field| field.trim().is_empty()) {
+        record.truncate(width);
+        return Ok(true);
+    }
+
+    Err(CsvError::Parse {
+        line: Some(line),
+        row,
+        field: None,
+        message: format!("found record with {} fields, but the header has {} fields", record.len(), width),
+    })
+}
+
+/// 把指定欄位的值轉成 JSON 布林值 (空白轉成 null)
+/// ## 參數
+/// - `object`: 單筆記錄的 JSON 物件
+/// - `options`: 讀取選項 (布林欄位與 true / false 字串)
+/// ## 返回
+/// - `Result<(), String>`: 有無法辨識的值時返回錯誤訊息
+fn _apply_boolean_columns(object: &mut Map<String, Value>, options: &ReadOptions) -> Result<(), String> {
+
+    for column in options.boolean_columns.iter() {
+
+        let key = match object.keys().find(|key| key.eq_ignore_ascii_case(column)) {
+            Some(key) => key.clone(),
+            None => continue,
+        };
+
+        let token = match &object[&key] {
+            Value::String(text) => text.trim().to_string(),
+            Value::Number(number) => number.to_string(),
+            Value::Bool(_) | Value::Null => continue,
+            _ => return Err(format!("column '{}' is not a single value", key)),
+        };
+
+        let boolean = if token.is_empty() {
+            Value::Null
+        } else {
+            match options.boolean_token(&token) {
+                Some(boolean) => Value::Bool(boolean),
+                None => return Err(format!("column '{}': unrecognized boolean value '{}'", key, token)),
+            }
+        };
+
+        object.insert(key, boolean);
+    }
+
+    Ok(())
+}
+
+/// 把標題列與資料列寫成 CSV 檔案 (UTF-8 無 BOM、以 \n 換行、只在必要時加引號)，寫入成功後記錄到該檔案的變更記錄
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `operation`: 操作名稱 (變更記錄用)，例如: "export_wide"
+/// - `path`: 輸出檔案的完整路徑
+/// - `headers`: 標題列
+/// - `rows`: 資料列
+/// - `overwrite`: 檔案已存在時是否覆蓋 (false 時返回 AlreadyExists)
+/// - `dry_run`: 只檢查並回報預計的變更，不實際寫入
+/// ## 返回
+/// - `Result<WriteReport, Error>`: 成功時返回寫入結果
+fn _write_csv_rows(app: &AppHandle, operation: &str, path: &Path, headers: &[String], rows: &[Vec<String>], overwrite: bool, dry_run: bool) -> Result<WriteReport, Error> {
+
+    let report = WriteReport {
+        dry_run,
+        path: path.to_string_lossy().to_string(),
+        rows: rows.len(),
+        columns: headers.to_vec(),
+    };
+
+    if !overwrite && path.exists() {
+        return Err(Error::new(ErrorKind::AlreadyExists, format!("File already exists: {}", report.path)));
+    }
+
+    if dry_run { return Ok(report); }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+
+    let mut writer = WriterBuilder::new()
+        .terminator(Terminator::Any(b'\n'))
+        .quote_style(QuoteStyle::Necessary)
+        .from_writer(file);
+
+    writer.write_record(headers)?;
+
+    for row in rows.iter() { writer.write_record(row)?; }
+
+    writer.flush()?;
+
+    if let Err(error) = append_change_log(app, path, operation, rows.len()) { warn!("Failed to write change log for {:?}: {}", path, error); }
+    Ok(report)
+}
+
+/// 取得記錄中某個欄位的不重複值 (不分大小寫排序)
+/// ## 參數
+/// - `records`: 記錄列表
+/// - `column`: 欄位名稱
+/// ## 返回
+/// - `Vec<String>`: 排序後的不重複值
+fn _distinct_values(records: &[&CsvRecord], column: &str) -> Vec<String> {
+
+    let set: HashSet<String> = records.iter()
+        .flat_map(|record| record.column_values(column).unwrap_or_default())
+        .collect();
+
+    let mut values: Vec<String> = set.into_iter().collect();
+    values.sort_by(|value1, value2| value1.to_lowercase().cmp(&value2.to_lowercase()).then(value1.cmp(value2)));
+    values
+}
+
+/// 找出 URL 無法解析的記錄 (空白的 URL 不檢查)
+/// ## 參數
+/// - `records`: 記錄列表
+/// - `lines`: 每筆記錄在檔案中的起始行號 (與 records 順序相同)
+/// ## 返回
+/// - `Vec<UrlWarning>`: 無法解析的記錄 (依檔案順序)
+fn _url_warnings(records: &[CsvRecord], lines: &[u64]) -> Vec<UrlWarning> {
+
+    records.iter().zip(lines.iter()).filter_map(|(record, line)| {
+        let url = record.url.trim();
+        if url.is_empty() { return None; }
+
+        Url::parse(url).err().map(|error| UrlWarning { line: *line, name: record.name.clone(), url: record.url.clone(), message: error.to_string() })
+    }).collect()
+}
+
+/// 透過快取解析 CSV 檔案 (修改時間與解析設定都相同時直接返回快取)
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `resource_path`: CSV 檔案的完整路徑
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Result<Arc<CacheEntry>, CsvError>`: 成功時返回解析結果，失敗時返回錯誤
+fn _cached_parse(app: &AppHandle, resource_path: &Path, options: &ReadOptions) -> Result<Arc<CacheEntry>, CsvError> {
+
+    let modified = _modified_time(resource_path)?;
+
+    app.state::<CsvCache>().get_or_parse(resource_path, modified, &_parse_signature(options), || {
+        let mut notes = ParseNotes::default();
+        let (lines, records): (Vec<u64>, Vec<CsvRecord>) = _parse_csv_file_with_lines(resource_path.to_string_lossy().to_string(), options, &mut notes)?.into_iter().unzip();
+        let distinct = _distinct_sets(&records);
+
+        Ok(CacheEntry { records, lines, notes, distinct })
+    })
+}
+
+/// 會影響解析結果的讀取選項 (作為快取鍵的一部分；排序、分頁等輸出選項不影響快取)
+/// ## 參數
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `String`: 設定的摘要字串
+fn _parse_signature(options: &ReadOptions) -> String {
+    format!("{:?}|{:?}|{}|{:?}|{}|{:?}|{:?}|{:?}|{}", options.delimiter, options.encoding, options.strict_trailing_field, options.level_rounding, options.parenthesized_negatives, options.numeric_columns, options.multi_value_separator, options.duplicate_headers, options.lenient)
+}
+
+/// 計算每個欄位 (小寫) 的不重複值
+/// ## 參數
+/// - `records`: 記錄
+/// ## 返回
+/// - `DistinctSets`: 欄位名稱 => 排序後的不重複值
+fn _distinct_sets(records: &[CsvRecord]) -> DistinctSets {
+    let references: Vec<&CsvRecord> = records.iter().collect();
+
+    CsvRecord::headers().iter()
+        .map(|column| (column.to_lowercase(), _distinct_values(&references, column)))
+        .collect()
+}
+
+/// 取得檔案的修改時間 (作為快取的失效依據)
+/// ## 參數
+/// - `path`: 檔案路徑
+/// ## 返回
+/// - `Result<SystemTime, Error>`: 成功時返回修改時間，失敗時返回錯誤
+fn _modified_time(path: &Path) -> Result<SystemTime, Error> {
+    path.metadata()?.modified()
+}
+
+/// 檢查欄位名稱是否為 CsvRecord 的欄位 (不分大小寫)
+/// ## 參數
+/// - `column`: 欄位名稱
+/// ## 返回
+/// - `Result<(), Error>`: 未知欄位時返回 InvalidInput
+fn _check_column(column: &str) -> Result<(), Error> {
+
+    if CsvRecord::headers().iter().any(|name| name.eq_ignore_ascii_case(column)) { return Ok(()); }
+
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!("Unknown column '{}', expected one of: {}", column, CsvRecord::headers().join(", ")),
+    ))
+}
+
+/// 解析並檢查鍵欄位的 JSON 陣列字串
+/// ## 參數
+/// - `key_columns_json`: 鍵欄位的 JSON 陣列字串，例如: ["name", "url"]
+/// ## 返回
+/// - `Result<Vec<String>, Error>`: 鍵欄位，格式錯誤、空陣列或有未知欄位時返回 InvalidInput
+fn _parse_key_columns(key_columns_json: &str) -> Result<Vec<String>, Error> {
+
+    let key_columns: Vec<String> = serde_json::from_str(key_columns_json)
+        .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("Invalid key columns JSON: {}", error)))?;
+
+    if key_columns.is_empty() { return Err(Error::new(ErrorKind::InvalidInput, "Key columns cannot be empty")); }
+    for column in key_columns.iter() { _check_column(column)?; }
+
+    Ok(key_columns)
+}
+
+/// 依鍵欄位把記錄分組，只留下超過一筆的群組 (比對方式同 duplicate_key_groups)
+/// ## 參數
+/// - `records`: (起始行號, 記錄)
+/// - `key_columns`: 鍵欄位 (已由 _parse_key_columns 檢查)
+/// ## 返回
+/// - `Vec<DuplicateKeyGroup>`: 重複的群組 (依第一次出現的行號排序)
+fn _duplicate_key_groups(records: &[(u64, CsvRecord)], key_columns: &[String]) -> Vec<DuplicateKeyGroup> {
+
+    let mut groups: Vec<DuplicateKeyGroup> = Vec::new();
+    let mut indexes: HashMap<Vec<String>, usize> = HashMap::new();
+
+    for (line, record) in records.iter() {
+
+        let key: Vec<String> = key_columns.iter().map(|column| _comparable_cell(record, column)).collect();
+
+        match indexes.get(&key) {
+            Some(index) => groups[*index].lines.push(*line),
+            None => {
+                indexes.insert(key.clone(), groups.len());
+                groups.push(DuplicateKeyGroup { key, lines: vec![*line] });
+            }
+        }
+    }
+
+    groups.into_iter().filter(|group| group.lines.len() > 1).collect()
+}
+
+/// 取得用來比對的欄位文字 (小寫、去除前後空白，多值欄位排序後以逗號合併)
+/// ## 參數
+/// - `record`: 單筆記錄
+/// - `column`: 欄位名稱
+/// ## 返回
+/// - `String`: 比對用的文字
+fn _comparable_cell(record: &CsvRecord, column: &str) -> String {
+
+    if let Some(values) = record.multi_values(column) {
+        let mut values: Vec<String> = values.iter().map(|value| value.trim().to_lowercase()).filter(|value| !value.is_empty()).collect();
+        values.sort();
+        return values.join(",");
+    }
+
+    record.cell(column).unwrap_or_default().trim().to_lowercase()
+}
+
+/// 取得 URL 開頭的 scheme (小寫，只認開頭符合「[A-Za-z][A-Za-z0-9+.-]*:」的部分)
+/// - 例如: "HTTP://example.com" => "http"、"example.com" => ""、"example.com/?next=http://x" => ""
+/// ## 參數
+/// - `url`: URL 字串
+/// ## 返回
+/// - `String`: scheme，開頭沒有 scheme 時返回空字串
+fn _url_scheme(url: &str) -> String {
+
+    let scheme = match url.split_once(':') {
+        Some((scheme, _)) => scheme,
+        None => return String::new(),
+    };
+
+    let mut chars = scheme.chars();
+    let is_scheme = chars.next().is_some_and(|char| char.is_ascii_alphabetic())
+        && chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '.' | '-'));
+
+    if is_scheme { scheme.to_lowercase() } else { String::new() }
+}
+
+/// 把分組後的記錄寫入 xlsx (每個工作表的第一列為粗體標題列，Level 寫成數字)
+/// ## 參數
+/// - `path`: 輸出檔案路徑
+/// - `headers`: 標題列
+/// - `sheets`: 工作表與其記錄
+/// ## 返回
+/// - `Result<(), XlsxError>`: 寫入失敗時返回錯誤
+fn _write_xlsx_sheets(path: &Path, headers: &[String], sheets: &[(XlsxSheet, Vec<&CsvRecord>)]) -> Result<(), XlsxError> {
+
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+
+    for (sheet, records) in sheets.iter() {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(&sheet.name)?;
+
+        for (column, header) in headers.iter().enumerate() { worksheet.write_string_with_format(0, column as u16, header, &bold)?; }
+
+        for (row, record) in records.iter().enumerate() {
+            let row = row as u32 + 1;
+
+            for (column, header) in headers.iter().enumerate() {
+                match header.as_str() {
+                    "Level" => worksheet.write_number(row, column as u16, record.level as f64)?,
+                    _ => worksheet.write_string(row, column as u16, record.cell(header).unwrap_or_default())?,
+                };
+            }
+        }
+    }
+
+    workbook.save(path)
+}
+
+/// 把 Category 轉換成合法且不重複的 xlsx 工作表名稱
+/// - 移除 `[ ] : * ? / \`、去掉開頭結尾的 `'`、避開保留名稱 History、最多 31 個字元，重複 (不分大小寫) 時加上 " (2)" 等編號
+/// ## 參數
+/// - `category`: Category 的值
+/// - `used_names`: 已使用的工作表名稱 (小寫)
+/// ## 返回
+/// - `String`: 工作表名稱
+fn _sheet_name(category: &str, used_names: &mut HashSet<String>) -> String {
+
+    let cleaned: String = category.chars().filter(|character| !matches!(character, '[' | ']' | ':' | '*' | '?' | '/' | '\\')).collect();
+    let cleaned = cleaned.trim().trim_matches('\'').trim();
+    let base = match cleaned {
+        "" => UNCATEGORIZED_SHEET.to_string(),
+        _ if cleaned.eq_ignore_ascii_case("History") => format!("{}_", cleaned),
+        _ => cleaned.to_string(),
+    };
+
+    let mut number = 1;
+    loop {
+        let suffix = if number == 1 { String::new() } else { format!(" ({})", number) };
+        let name: String = base.chars().take(SHEET_NAME_MAX_LENGTH - suffix.chars().count()).collect::<String>() + &suffix;
+
+        if used_names.insert(name.to_lowercase()) { return name; }
+        number += 1;
+    }
+}
+
+/// 把記錄轉換成固定格式的資料列 (canonicalize_file 的規則；Name、URL 都相同時再依整列排序)
+/// ## 參數
+/// - `records`: 記錄
+/// - `headers`: 標題列
+/// ## 返回
+/// - `Vec<Vec<String>>`: 排序並正規化後的資料列
+fn _canonical_rows(records: &[CsvRecord], headers: &[String]) -> Vec<Vec<String>> {
+
+    let mut rows: Vec<(&CsvRecord, Vec<String>)> = records.iter().map(|record| {
+        let row = headers.iter().map(|header| match record.multi_values(header) {
+            Some(values) => join_multi_value(&values.iter().map(|value| value.trim()).filter(|value| !value.is_empty()).collect::<Vec<&str>>()),
+            None => record.cell(header).unwrap_or_default().trim().to_string(),
+        }).collect();
+        (record, row)
+    }).collect();
+
+    rows.sort_by(|(record1, row1), (record2, row2)| {
+        let (name1, name2) = (record1.name.trim(), record2.name.trim());
+        name1.to_lowercase().cmp(&name2.to_lowercase()).then(name1.cmp(name2)).then(record1.url.trim().cmp(record2.url.trim())).then(row1.cmp(row2))
+    });
+
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+/// 把記錄轉成 CSV 資料列 (依 CsvRecord::headers() 的欄位順序)
+/// ## 參數
+/// - `records`: 記錄列表
+/// ## 返回
+/// - `Vec<Vec<String>>`: 資料列
+fn _records_to_rows(records: &[CsvRecord]) -> Vec<Vec<String>> {
+    let headers = CsvRecord::headers();
+    records.iter().map(|record| headers.iter().map(|header| record.cell(header).unwrap_or_default()).collect()).collect()
+}
+
+/// 依 notesDelimiter / notesLabels 把 Notes 拆成陣列或物件 (每一段會去除前後空白)
+/// - 例如: "Summary | Details" => ["Summary", "Details"]，沒有分隔符號時為只有一個元素的陣列
+/// ## 參數
+/// - `notes`: Notes 的文字
+/// - `options`: 讀取選項
+/// ## 返回
+/// - `Option<Value>`: 沒有設定 notesDelimiter (或為空字串) 時返回 None
+fn _split_notes(notes: &str, options: &ReadOptions) -> Option<Value> {
+
+    let delimiter = options.notes_delimiter.as_deref().filter(|delimiter| !delimiter.is_empty())?;
+    let parts: Vec<&str> = notes.split(delimiter).map(str::trim).collect();
+
+    if options.notes_labels.is_empty() { return Some(Value::from(parts)); }
+
+    let last = options.notes_labels.len() - 1;
+    let object: Map<String, Value> = options.notes_labels.iter().enumerate().map(|(index, label)| {
+        let part = if index == last {
+            parts.get(index..).map(|rest| rest.join(delimiter)).unwrap_or_default()
+        } else {
+            parts.get(index).map(|part| part.to_string()).unwrap_or_default()
+        };
+        (label.clone(), Value::from(part))
+    }).collect();
+
+    Some(Value::Object(object))
+}
+
+/// 遞迴攤平 JSON 值 (鍵以「.」連接，空陣列、空物件轉成 null)
+/// ## 參數
+/// - `path`: 目前的鍵 (最上層為空字串)
+/// - `value`: 要攤平的值
+/// - `flat`: 輸出的物件
+fn _flatten_value(path: String, value: Value, flat: &mut Map<String, Value>) {
+
+    let child_path = |key: String| if path.is_empty() { key } else { format!("{}.{}", path, key) };
+
+    match value {
+        Value::Object(object) if object.is_empty() && !path.is_empty() => { flat.insert(path, Value::Null); }
+        Value::Array(values) if values.is_empty() && !path.is_empty() => { flat.insert(path, Value::Null); }
+        Value::Object(object) => for (key, child) in object { _flatten_value(child_path(key), child, flat); },
+        Value::Array(values) => for (index, child) in values.into_iter().enumerate() { _flatten_value(child_path(index.to_string()), child, flat); },
+        scalar => { flat.insert(path, scalar); }
+    }
+}
+
+/// 把多值欄位的陣列合併成單一字串
+/// - 例如: "Platform": ["Windows", "Linux"] => "Platform": "Windows, Linux" (分隔符號為 ", ")
+/// ## 參數
+/// - `object`: 單筆記錄的 JSON 物件
+/// - `separator`: 分隔符號
+fn _join_multi_values(object: &mut Map<String, Value>, separator: &str) {
+
+    for column in CsvRecord::MULTI_VALUE_COLUMNS.iter() {
+
+        let joined = match object.get(*column) {
+            Some(Value::Array(values)) => values.iter().filter_map(|value| value.as_str()).collect::<Vec<&str>>().join(separator),
+            _ => continue,
+        };
+
+        object.insert(column.to_string(), Value::String(joined));
+    }
+}
+
+/// 檢查欄位名稱是否為多值欄位 (Platform / Type / OS / Language / Category)
+/// ## 參數
+/// - `column`: 欄位名稱 (不分大小寫)
+/// ## 返回
+/// - `Result<(), Error>`: 不是多值欄位時返回 InvalidInput
+fn _check_multi_value_column(column: &str) -> Result<(), Error> {
+
+    if CsvRecord::MULTI_VALUE_COLUMNS.iter().any(|name| name.eq_ignore_ascii_case(column)) { return Ok(()); }
+
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!("Unknown multi-value column '{}', expected one of: {}", column, CsvRecord::MULTI_VALUE_COLUMNS.join(", ")),
+    ))
+}
+
+/// 把 glob 字串列表編譯成 GlobSet
+/// ## 參數
+/// - `patterns`: glob 字串列表
+/// ## 返回
+/// - `Result<Option<GlobSet>, Error>`: 空列表時返回 None，格式錯誤時返回 InvalidInput
+fn _glob_set(patterns: &[String]) -> Result<Option<GlobSet>, Error> {
+
+    if patterns.is_empty() { return Ok(None); }
+
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => { builder.add(glob); }
+            Err(error) => return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid glob pattern '{}': {}", pattern, error))),
+        }
+    }
+
+    match builder.build() {
+        Ok(set) => Ok(Some(set)),
+        Err(error) => Err(Error::new(ErrorKind::InvalidInput, error.to_string())),
+    }
+}
+
+/// 計算每一筆記錄的主要分隔符號 (引號內的字元不計算，跨行的引號欄位視為同一筆)
+/// ## 參數
+/// - `content`: 檔案內容
+/// - `limit`: 最多取樣的記錄數
+/// ## 返回
+/// - `Vec<(usize, char)>`: (記錄起始行號, 主要分隔符號)，沒有任何分隔符號的行會被略過
+fn _line_dominant_delimiters(content: &str, limit: usize) -> Vec<(usize, char)> {
+
+    let mut dominants: Vec<(usize, char)> = Vec::new();
+    let mut counts = [0usize; DELIMITER_CANDIDATES.len()];
+    let mut in_quotes = false;
+    let mut line_number = 1;
+    let mut record_line = 1;
+
+    for char in content.chars() {
+        match char {
+            '"' => in_quotes = !in_quotes,
+            '\n' => {
+                line_number += 1;
+                if in_quotes { continue; }
+
+                if let Some(delimiter) = _dominant_delimiter(&counts) { dominants.push((record_line, delimiter)); }
+                if dominants.len() >= limit { return dominants; }
+
+                counts = [0usize; DELIMITER_CANDIDATES.len()];
+                record_line = line_number;
+            }
+            _ if !in_quotes => {
+                if let Some(index) = DELIMITER_CANDIDATES.iter().position(|candidate| *candidate == char) { counts[index] += 1; }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(delimiter) = _dominant_delimiter(&counts) { dominants.push((record_line, delimiter)); }
+    dominants
+}
+
+/// 從各候選字元的出現次數中取出最多的那一個 (同數量時以候選順序優先)
+/// ## 參數
+/// - `counts`: 各候選分隔符號的出現次數
+/// ## 返回
+/// - `Option<char>`: 主要分隔符號，完全沒有出現時返回 None
+fn _dominant_delimiter(counts: &[usize; DELIMITER_CANDIDATES.len()]) -> Option<char> {
+    let (index, count) = counts.iter().enumerate().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))?;
+    if *count == 0 { return None; }
+    Some(DELIMITER_CANDIDATES[index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 測試用的暫存檔 (檔名帶有行程編號，結束時自動刪除)
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, bytes: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("csv-reader-{}-{}", std::process::id(), name));
+            fs::write(&path, bytes).expect("failed to write temp file");
+            Self(path)
+        }
+
+        fn path(&self) -> String {
+            self.0.to_string_lossy().to_string()
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    /// 測試用的暫存資料夾 (名稱帶有行程編號，結束時連同內容自動刪除)
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("csv-reader-{}-{}", std::process::id(), name));
+            create_dir_all(&path).expect("failed to create temp dir");
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// fixtures 資料夾內檔案的完整路徑
+    fn fixture(name: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn empty_trailing_field_is_accepted() {
+        let mut names: Vec<String> = vec![];
+        let count = _for_each_csv_record::<CsvRecord, _>(fixture("TrailingComma.csv"), &ReadOptions::default(), &mut ParseNotes::default(), |_, record| {
+            names.push(record.name);
+            Ok(())
+        }).expect("fixture should parse");
+
+        assert_eq!(count, 3);
+        assert_eq!(names, ["lsof", "kill", "top"]);
+    }
+
+    #[test]
+    fn non_empty_extra_field_is_rejected_with_row_number() {
+        let file = TempFile::new("extra-field.csv", b"Name,Notes,URL,Type,Level\nlsof,Notes,https://a.example,CLI,4,\nkill,Notes,https://b.example,CLI,2,extra\n");
+        let error = _for_each_csv_record::<CsvRecord, _>(file.path(), &ReadOptions::default(), &mut ParseNotes::default(), |_, _| Ok(())).unwrap_err();
+
+        match &error {
+            CsvError::Parse { line, row, .. } => {
+                assert_eq!(*line, Some(3));
+                assert_eq!(*row, Some(2));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error.to_string().starts_with("row 2: "), "{}", error);
+    }
+
+    #[test]
+    fn boolean_columns_convert_tokens_and_reject_unknown_values() {
+        let options = ReadOptions { boolean_columns: vec![String::from("active")], ..ReadOptions::default() };
+
+        for (value, expected) in [(Value::from("Yes"), Value::Bool(true)), (Value::from("0"), Value::Bool(false)), (Value::from(1), Value::Bool(true)), (Value::from(""), Value::Null)] {
+            let mut object = Map::new();
+            object.insert(String::from("Active"), value);
+
+            _apply_boolean_columns(&mut object, &options).expect("token should be recognized");
+            assert_eq!(object["Active"], expected);
+        }
+
+        let mut object = Map::new();
+        object.insert(String::from("Active"), Value::from("maybe"));
+
+        let error = _apply_boolean_columns(&mut object, &options).unwrap_err();
+        assert!(error.contains("unrecognized boolean value 'maybe'"), "{}", error);
+    }
+
+    #[test]
+    fn url_scheme_only_reads_the_leading_scheme() {
+        assert_eq!(_url_scheme("HTTP://example.com"), "http");
+        assert_eq!(_url_scheme("https://example.com/?next=http://x"), "https");
+        assert_eq!(_url_scheme("example.com/?next=http://x"), "");
+        assert_eq!(_url_scheme("example.com"), "");
+        assert_eq!(_url_scheme("svn+ssh://example.com"), "svn+ssh");
+    }
+
+    #[test]
+    fn prune_log_files_only_removes_files_past_retention() {
+        let dir = TempDir::new("prune-logs");
+        for name in ["20260131.log", "20260102.log", "20260101.log", "20251201.log", "notes.log", "20251201.txt"] {
+            fs::write(dir.0.join(name), b"log").expect("failed to seed log file");
+        }
+
+        let today = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        let removed = prune_log_files(&dir.0, today, 30);
+
+        assert_eq!(removed, ["20251201.log", "20260101.log"]);
+        for name in ["20260131.log", "20260102.log", "notes.log", "20251201.txt"] {
+            assert!(dir.0.join(name).exists(), "{} should be kept", name);
+        }
+    }
+
+    #[test]
+    fn parenthesized_negatives_only_convert_numeric_columns() {
+        let headers = StringRecord::from(vec!["Name", "Amount", "Notes"]);
+        let options = ReadOptions { numeric_columns: vec![String::from("amount")], ..ReadOptions::default() };
+        let indexes = _numeric_column_indexes(&headers, &options);
+
+        let mut record = StringRecord::from(vec!["lsof", "(1,234.50)", "(1,234.50)"]);
+        _convert_parenthesized_negatives(&mut record, &indexes, &headers, 2, Some(1)).expect("numeric column should convert");
+        assert_eq!(record, StringRecord::from(vec!["lsof", "-1234.50", "(1,234.50)"]));
+
+        let mut record = StringRecord::from(vec!["kill", "(abc)", "(see docs)"]);
+        let error = _convert_parenthesized_negatives(&mut record, &indexes, &headers, 3, Some(2)).unwrap_err();
+        match error {
+            CsvError::Parse { line, row, field, message } => {
+                assert_eq!((line, row, field.as_deref()), (Some(3), Some(2), Some("Amount")));
+                assert_eq!(message, "invalid parenthesized number '(abc)'");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    /// 依 name_comparator 排序名稱
+    fn sorted_names<'a>(locale: Option<&str>, names: &[&'a str]) -> Vec<&'a str> {
+        let compare = name_comparator(locale).expect("locale should be supported");
+        let mut names = names.to_vec();
+        names.sort_by(|name1, name2| compare(name1, name2));
+        names
+    }
+
+    #[test]
+    fn name_comparator_orders_by_locale() {
+        assert_eq!(sorted_names(Some("zh"), &["張", "李", "王"]), ["李", "王", "張"]);
+        assert_eq!(sorted_names(Some("de"), &["Zebra", "Äpfel", "Apfel"]), ["Apfel", "Äpfel", "Zebra"]);
+        assert_eq!(sorted_names(Some("sv"), &["Zebra", "Äpfel", "Apfel"]), ["Apfel", "Zebra", "Äpfel"]);
+        assert_eq!(sorted_names(None, &["Äpfel", "bar", "Apfel"]), ["Apfel", "bar", "Äpfel"]);
+    }
+
+    #[test]
+    fn name_comparator_falls_back_for_unknown_or_blank_locales() {
+        assert_eq!(sorted_names(Some("xx"), &["Zebra", "Äpfel", "Apfel"]), ["Apfel", "Äpfel", "Zebra"]);
+        assert_eq!(sorted_names(Some("  "), &["Äpfel", "bar", "Apfel"]), ["Apfel", "bar", "Äpfel"]);
+
+        let error = name_comparator(Some("not a locale")).err().expect("invalid locale should be rejected");
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn multi_value_cells_support_escaped_commas_and_a_custom_separator() {
+        let csv = b"Name,Notes,URL,Type,Level\nlsof,Notes,https://a.example,\"Dev, Tools\",4\nkill,Notes,https://b.example,\"Dev\\, Tools, CLI\",2\ntop,Notes,https://c.example,\"Dev, Tools|CLI\",3\n";
+        let file = TempFile::new("multi-value.csv", csv);
+
+        let read_types = |options: &ReadOptions| {
+            let mut types: Vec<Vec<String>> = vec![];
+            _for_each_csv_record::<CsvRecord, _>(file.path(), options, &mut ParseNotes::default(), |_, record| { types.push(record.r#type); Ok(()) }).expect("file should parse");
+            types
+        };
+
+        assert_eq!(read_types(&ReadOptions::default()), [vec!["Dev", "Tools"], vec!["Dev, Tools", "CLI"], vec!["Dev", "Tools|CLI"]]);
+
+        let options = ReadOptions { multi_value_separator: Some(String::from("|")), ..ReadOptions::default() };
+        assert_eq!(read_types(&options), [vec!["Dev, Tools"], vec!["Dev\\, Tools, CLI"], vec!["Dev, Tools", "CLI"]]);
+    }
+
+    /// 只有 Name 與 Type / OS 的測試用記錄
+    fn facet_record(name: &str, types: &[&str], os: &[&str]) -> CsvRecord {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        CsvRecord { name: name.to_string(), notes: String::new(), url: String::new(), level: 1, example: None, platform: vec![], r#type: strings(types), os: strings(os), language: vec![], category: vec![] }
+    }
+
+    #[test]
+    fn matches_facets_accepts_any_of_several_values() {
+        let record = facet_record("lsof", &["CLI", "Network"], &["Linux", "macOS"]);
+
+        let filters = parse_facet_filters(r#"{"type": ["gui", "network"]}"#).unwrap();
+        assert!(matches_facets(&record, &filters));
+
+        let filters = parse_facet_filters(r#"{"type": ["CLI"], "os": ["Windows"]}"#).unwrap();
+        assert!(!matches_facets(&record, &filters));
+
+        let filters = parse_facet_filters(r#"{"type": {"include": ["CLI"], "exclude": ["network"]}}"#).unwrap();
+        assert!(!matches_facets(&record, &filters));
+    }
+
+    #[test]
+    fn matches_facets_handles_empty_multi_value_fields() {
+        let record = facet_record("kill", &[], &["Linux"]);
+
+        assert!(!matches_facets(&record, &parse_facet_filters(r#"{"type": ["CLI"]}"#).unwrap()));
+        assert!(matches_facets(&record, &parse_facet_filters(r#"{"type": []}"#).unwrap()));
+        assert!(matches_facets(&record, &parse_facet_filters(r#"{"type": {"exclude": ["Deprecated"]}}"#).unwrap()));
+    }
+
+    #[test]
+    fn field_filters_merge_values_with_existing_rules() {
+        let filters = _field_filters(String::from("Type"), vec![String::from("CLI")], r#"{"type": {"include": ["GUI"], "exclude": ["Deprecated"]}}"#).unwrap();
+
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters["Type"].include(), [String::from("CLI"), String::from("GUI")]);
+        assert_eq!(filters["Type"].exclude(), [String::from("Deprecated")]);
+
+        assert!(matches_facets(&facet_record("top", &["GUI"], &[]), &filters));
+        assert!(!matches_facets(&facet_record("ps", &["CLI", "Deprecated"], &[]), &filters));
+        assert!(!matches_facets(&facet_record("df", &[], &[]), &filters));
+        assert!(_field_filters(String::from("Name"), vec![], "").is_err());
+    }
+
+    /// 依讀取選項解析檔案，返回每筆記錄的 (Name, Notes)
+    fn read_names_and_notes(path: String, options: &ReadOptions) -> Result<Vec<(String, String)>, CsvError> {
+        let mut rows = vec![];
+        _for_each_csv_record::<CsvRecord, _>(path, options, &mut ParseNotes::default(), |_, record| { rows.push((record.name, record.notes)); Ok(()) })?;
+        Ok(rows)
+    }
+
+    #[test]
+    fn utf8_bom_is_skipped() {
+        let file = TempFile::new("bom.csv", b"\xEF\xBB\xBFName,Notes,URL,Type,Level\nlsof,List open files,https://a.example,CLI,4\n");
+        let rows = read_names_and_notes(file.path(), &ReadOptions::default()).expect("BOM file should parse");
+
+        assert_eq!(rows, [(String::from("lsof"), String::from("List open files"))]);
+    }
+
+    #[test]
+    fn big5_file_is_decoded_while_streaming() {
+        let (bytes, _, had_errors) = encoding_rs::BIG5.encode("Name,Notes,URL,Type,Level\n終端機,列出開啟的檔案,https://a.example,CLI,4\n");
+        assert!(!had_errors);
+
+        let file = TempFile::new("big5.csv", &bytes);
+        let options = ReadOptions { encoding: Some(String::from("big5")), ..ReadOptions::default() };
+        let rows = read_names_and_notes(file.path(), &options).expect("Big5 file should parse");
+
+        assert_eq!(rows, [(String::from("終端機"), String::from("列出開啟的檔案"))]);
+    }
+
+    #[test]
+    fn invalid_bytes_report_the_line() {
+        let file = TempFile::new("invalid-big5.csv", b"Name,Notes,URL,Type,Level\nlsof,Notes,https://a.example,CLI,4\nkill\x80,Notes,https://b.example,CLI,2\n");
+        let options = ReadOptions { encoding: Some(String::from("big5")), ..ReadOptions::default() };
+
+        match read_names_and_notes(file.path(), &options).unwrap_err() {
+            CsvError::Parse { line, message, .. } => {
+                assert_eq!(line, Some(3));
+                assert!(message.contains("Big5"), "{}", message);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn change_log_file_rejects_traversal_names() {
+        let dir = TempDir::new("change-log");
+        let changes_dir = dir.0.join("changes");
+
+        assert_eq!(_change_log_file(&changes_dir, "Tools.csv").unwrap(), changes_dir.join("Tools.csv.jsonl"));
+
+        for filename in ["../../x", "..", "sub/Tools.csv", "sub\\Tools.csv", "/etc/passwd", ""] {
+            let error = _change_log_file(&changes_dir, filename).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput, "{:?}", filename);
+        }
+    }
+
+    #[test]
+    fn raw_records_are_decoded_without_bom() {
+        let file = TempFile::new("raw-bom.csv", b"\xEF\xBB\xBFName;Notes\nlsof;List open files\n");
+        let options = ReadOptions { delimiter: Some(String::from(";")), ..ReadOptions::default() };
+        let (headers, records) = _read_raw_records(&file.0, &options).expect("BOM file should parse");
+
+        assert_eq!(headers, StringRecord::from(vec!["Name", "Notes"]));
+        assert_eq!(records, [StringRecord::from(vec!["lsof", "List open files"])]);
+    }
+
+    #[test]
+    fn raw_records_decode_big5() {
+        let (bytes, _, _) = encoding_rs::BIG5.encode("名稱,備註\n終端機,列出開啟的檔案\n");
+        let file = TempFile::new("raw-big5.csv", &bytes);
+        let options = ReadOptions { encoding: Some(String::from("big5")), ..ReadOptions::default() };
+
+        let (headers, records) = _read_raw_records(&file.0, &options).expect("Big5 file should parse");
+        assert_eq!(headers, StringRecord::from(vec!["名稱", "備註"]));
+        assert_eq!(records, [StringRecord::from(vec!["終端機", "列出開啟的檔案"])]);
+        assert_eq!(count_csv_rows(&file.0, &options).unwrap(), 1);
+    }
+
+    #[test]
+    fn validate_files_isolates_errors_and_panics() {
+        let files: Vec<String> = ["b.csv", "Panic.csv", "a.csv", "error.csv"].iter().map(|file| file.to_string()).collect();
+
+        let reports = _validate_files(&files, 2, |file| match file {
+            "Panic.csv" => panic!("broken parser"),
+            "error.csv" => Err(Error::new(ErrorKind::InvalidData, "row 2: bad")),
+            "b.csv" => Ok(vec![String::from("duplicate header 'Name'")]),
+            _ => Ok(vec![]),
+        }).unwrap();
+
+        let files: Vec<&str> = reports.iter().map(|report| report.file.as_str()).collect();
+        assert_eq!(files, ["a.csv", "b.csv", "error.csv", "Panic.csv"]);
+
+        assert_eq!((reports[0].problems.len(), reports[0].error.as_deref()), (0, None));
+        assert_eq!(reports[1].problems, ["duplicate header 'Name'"]);
+        assert_eq!(reports[2].error.as_deref(), Some("row 2: bad"));
+        assert_eq!(reports[3].error.as_deref(), Some("Validation panicked: broken parser"));
+    }
+
+    #[test]
+    fn url_warnings_report_the_file_line_after_skipped_rows() {
+        let file = TempFile::new("url-warnings.csv", b"Name,Notes,URL,Type,Level\nlsof,Notes,https://a.example,CLI,4\nkill,Notes,https://b.example,CLI,2,extra\ntop,Notes,http://[bad,CLI,3\n");
+        let options = ReadOptions { lenient: true, ..ReadOptions::default() };
+        let mut notes = ParseNotes::default();
+
+        let (lines, records): (Vec<u64>, Vec<CsvRecord>) = _parse_csv_file_with_lines(file.path(), &options, &mut notes).expect("file should parse").into_iter().unzip();
+        let warnings = _url_warnings(&records, &lines);
+
+        assert_eq!(notes.skipped.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].line, warnings[0].name.as_str()), (4, "top"));
+    }
+
+    #[test]
+    fn sheet_name_truncates_long_categories() {
+        let mut used_names = HashSet::new();
+        let name = _sheet_name(&"A".repeat(40), &mut used_names);
+
+        assert_eq!(name, "A".repeat(31));
+    }
+
+    #[test]
+    fn sheet_name_removes_invalid_characters() {
+        let mut used_names = HashSet::new();
+
+        assert_eq!(_sheet_name("Dev/Ops: [Tools]*?\\", &mut used_names), "DevOps Tools");
+        assert_eq!(_sheet_name("'[]'", &mut used_names), UNCATEGORIZED_SHEET);
+        assert_eq!(_sheet_name("history", &mut used_names), "history_");
+    }
+
+    #[test]
+    fn sheet_name_numbers_names_that_collide_after_truncation() {
+        let mut used_names = HashSet::new();
+        let prefix = "Networking and Remote Administr";
+        assert_eq!(prefix.chars().count(), 31);
+
+        let first = _sheet_name(&format!("{}ation Tools", prefix), &mut used_names);
+        let second = _sheet_name(&format!("{}ation Utilities", prefix), &mut used_names);
+        let third = _sheet_name(&prefix.to_uppercase(), &mut used_names);
+
+        assert_eq!(first, prefix);
+        assert_eq!(second, "Networking and Remote Admin (2)");
+        assert_eq!(third, "NETWORKING AND REMOTE ADMIN (3)");
+        assert!([&first, &second, &third].iter().all(|name| name.chars().count() <= 31));
+    }
+
+    #[test]
+    fn resolve_duplicate_headers_rejects_duplicates_in_error_mode() {
+        let mut headers = StringRecord::from(vec!["Name", "URL", "Name"]);
+        let error = _resolve_duplicate_headers(&mut headers, DuplicateHeaders::Error).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().contains("Name"));
+        assert_eq!(headers, StringRecord::from(vec!["Name", "URL", "Name"]));
+
+        let mut unique = StringRecord::from(vec!["Name", "URL"]);
+        assert!(_resolve_duplicate_headers(&mut unique, DuplicateHeaders::Error).is_ok());
+    }
+
+    #[test]
+    fn resolve_duplicate_headers_numbers_duplicates_in_rename_mode() {
+        let mut headers = StringRecord::from(vec!["Name", "URL", "Name", "Name"]);
+        _resolve_duplicate_headers(&mut headers, DuplicateHeaders::Rename).unwrap();
+
+        assert_eq!(headers, StringRecord::from(vec!["Name", "URL", "Name_2", "Name_3"]));
+    }
+
+    #[test]
+    fn resolve_duplicate_headers_skips_names_that_already_exist() {
+        let mut headers = StringRecord::from(vec!["Name", "Name", "Name_2"]);
+        _resolve_duplicate_headers(&mut headers, DuplicateHeaders::Rename).unwrap();
+
+        assert_eq!(headers, StringRecord::from(vec!["Name", "Name_3", "Name_2"]));
+    }
+
+    #[test]
+    fn line_dominant_delimiters_reports_each_record() {
+        let dominants = _line_dominant_delimiters("Name,URL,Type\nA;B;C\nplain\nx|y\n", usize::MAX);
+
+        assert_eq!(dominants, vec![(1, ','), (2, ';'), (4, '|')]);
+        assert_eq!(_line_dominant_delimiters("a,b\nc,d\ne,f", 2), vec![(1, ','), (2, ',')]);
+    }
+
+    #[test]
+    fn line_dominant_delimiters_ignores_quoted_fields() {
+        let content = "Name,Note\n\"a;b;c\",d\n\"multi\nline;x;y\",z\nnext;1\n";
+        let dominants = _line_dominant_delimiters(content, usize::MAX);
+
+        assert_eq!(dominants, vec![(1, ','), (2, ','), (3, ','), (5, ';')]);
+    }
+
+    #[test]
+    fn line_dominant_delimiters_breaks_ties_by_candidate_order() {
+        let dominants = _line_dominant_delimiters("a,b;c\td\na;b\tc\na\tb\tc;d;e\n", usize::MAX);
+
+        assert_eq!(dominants, vec![(1, ','), (2, ';'), (3, ';')]);
+    }
+
+    #[test]
+    fn flatten_value_joins_nested_keys() {
+        let value = serde_json::json!({
+            "Name": "nmap",
+            "Meta": { "Level": 3, "Source": { "Site": "github" } },
+            "Platform": ["Windows", "Linux"],
+            "Matrix": [[1, 2], { "Key": true }],
+        });
+        let mut flat = Map::new();
+        _flatten_value(String::new(), value, &mut flat);
+
+        let expected = serde_json::json!({
+            "Name": "nmap",
+            "Meta.Level": 3,
+            "Meta.Source.Site": "github",
+            "Platform.0": "Windows",
+            "Platform.1": "Linux",
+            "Matrix.0.0": 1,
+            "Matrix.0.1": 2,
+            "Matrix.1.Key": true,
+        });
+        assert_eq!(Value::Object(flat), expected);
+    }
+
+    #[test]
+    fn flatten_value_keeps_empty_containers_as_null() {
+        let value = serde_json::json!({ "Name": "nmap", "Platform": [], "Meta": {}, "Nested": { "Tags": [] } });
+        let mut flat = Map::new();
+        _flatten_value(String::new(), value, &mut flat);
+
+        assert_eq!(Value::Object(flat), serde_json::json!({ "Name": "nmap", "Platform": null, "Meta": null, "Nested.Tags": null }));
+
+        let mut empty = Map::new();
+        _flatten_value(String::new(), serde_json::json!({}), &mut empty);
+        assert!(empty.is_empty());
+    }
+
+    fn key_record(name: &str, url: &str, types: &[&str]) -> CsvRecord {
+        CsvRecord { url: url.to_string(), ..facet_record(name, types, &[]) }
+    }
+
+    #[test]
+    fn duplicate_key_groups_compare_every_key_column() {
+        let records = vec![
+            (2, key_record("nmap", "https://nmap.org", &[])),
+            (3, key_record("nmap", "https://nmap.org/download", &[])),
+            (4, key_record("curl", "https://curl.se", &[])),
+            (5, key_record("nmap", "https://nmap.org", &[])),
+        ];
+        let key_columns = _parse_key_columns(r#"["Name", "url"]"#).unwrap();
+        let groups = _duplicate_key_groups(&records, &key_columns);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].key, vec!["nmap", "https://nmap.org"]);
+        assert_eq!(groups[0].lines, vec![2, 5]);
+    }
+
+    #[test]
+    fn duplicate_key_groups_ignore_case_whitespace_and_value_order() {
+        let records = vec![
+            (2, key_record("  Nmap ", "HTTPS://NMAP.ORG", &["CLI", "Network"])),
+            (3, key_record("nmap", "https://nmap.org", &["network ", "cli"])),
+            (4, key_record("nmap", "https://nmap.org", &["CLI"])),
+        ];
+
+        let groups = _duplicate_key_groups(&records, &_parse_key_columns(r#"["name", "url"]"#).unwrap());
+        assert_eq!(groups.iter().map(|group| group.lines.clone()).collect::<Vec<_>>(), vec![vec![2, 3, 4]]);
+
+        let groups = _duplicate_key_groups(&records, &_parse_key_columns(r#"["name", "type"]"#).unwrap());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].key, vec!["nmap", "cli,network"]);
+        assert_eq!(groups[0].lines, vec![2, 3]);
+    }
+
+    #[test]
+    fn parse_key_columns_rejects_unknown_or_empty_columns() {
+        let error = _parse_key_columns(r#"["Name", "Homepage"]"#).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("Homepage"));
+
+        assert_eq!(_parse_key_columns("[]").unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(_parse_key_columns("name").unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn initial_log_level_falls_back_to_debug_with_a_warning() {
+        let mut warnings = Vec::new();
+
+        let config = LoggingConfig { level: Some(String::from(" Info ")), retention_days: None };
+        assert_eq!(_initial_log_level(&config, &mut warnings), LevelFilter::Info);
+        assert_eq!(_initial_log_level(&LoggingConfig::default(), &mut warnings), LevelFilter::Debug);
+        assert!(warnings.is_empty());
+
+        let config = LoggingConfig { level: Some(String::from("verbose")), retention_days: None };
+        assert_eq!(_initial_log_level(&config, &mut warnings), LevelFilter::Debug);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'verbose'"));
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{info, warn};
+use notify::event::ModifyKind;
+use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::{AppHandle, Emitter};
+
+use crate::library::utils::folder_files;
+
+/// 資料夾有變動時發出的事件名稱 (內容為更新後的 CSV 檔名列表)
+pub const CSV_FOLDER_CHANGED_EVENT: &str = "csv-folder-changed";
+
+/// 收到第一個變動後，等待這段時間內都沒有新的變動才發出事件 (避免存檔一次就觸發十次)
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 監看 document 資料夾的檔案新增、更名與刪除
+/// - 結束時先停止 notify 的監看 (關閉通道)，再等待處理事件的執行緒結束
+#[derive(Default)]
+pub struct FolderWatcher {
+    running: Mutex<Option<(RecommendedWatcher, JoinHandle<()>)>>,
+}
+
+impl FolderWatcher {
+
+    /// 開始監看資料夾 (已在監看時會先停止舊的監看)
+    /// ## 參數
+    /// - `app`: Tauri 應用的 AppHandle
+    /// - `folder`: 要監看的資料夾完整路徑
+    /// ## 返回
+    /// - `Result<(), notify::Error>`: 無法監看資料夾時返回錯誤
+    pub fn start(&self, app: AppHandle, folder: PathBuf) -> Result<(), notify::Error> {
+
+        self.stop();
+
+        let (sender, receiver) = channel();
+        let mut watcher = recommended_watcher(sender)?;
+        watcher.watch(&folder, RecursiveMode::NonRecursive)?;
+
+        info!("Watching folder: {}", folder.display());
+
+        let handle = thread::spawn(move || _debounce_events(app, folder, receiver));
+        *self.running.lock().unwrap_or_else(PoisonError::into_inner) = Some((watcher, handle));
+
+        Ok(())
+    }
+
+    /// 停止監看並等待執行緒結束 (沒有在監看時不做任何事)
+    pub fn stop(&self) {
+
+        let running = self.running.lock().unwrap_or_else(PoisonError::into_inner).take();
+
+        if let Some((watcher, handle)) = running {
+            drop(watcher);
+            if handle.join().is_err() { warn!("Folder watcher thread panicked"); }
+        }
+    }
+}
+
+/// 收集 notify 的事件，一段時間內沒有新的變動時才發出一次 csv-folder-changed 事件
+/// - notify 的監看被停止後通道會關閉，此時執行緒結束
+/// ## 參數
+/// - `app`: Tauri 應用的 AppHandle
+/// - `folder`: 監看的資料夾完整路徑
+/// - `receiver`: notify 事件的接收端
+fn _debounce_events(app: AppHandle, folder: PathBuf, receiver: Receiver<notify::Result<Event>>) {
+
+    while let Ok(event) = receiver.recv() {
+
+        if !_is_listing_change(&event) { continue; }
+
+        loop {
+            match receiver.recv_timeout(DEBOUNCE) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        match folder_files(folder.clone(), Some("csv"), &[], &[]) {
+            Ok(files) => if let Err(error) = app.emit(CSV_FOLDER_CHANGED_EVENT, files) { warn!("Failed to emit {}: {}", CSV_FOLDER_CHANGED_EVENT, error); },
+            Err(error) => warn!("Failed to list folder {}: {}", folder.display(), error),
+        }
+    }
+}
+
+/// 是否為會改變檔名列表的事件 (新增、刪除、更名)
+/// ## 參數
+/// - `event`: notify 的事件 (或錯誤)
+/// ## 返回
+/// - `bool`: 是否需要重新列出檔名
+fn _is_listing_change(event: &notify::Result<Event>) -> bool {
+    match event {
+        Ok(event) => matches!(event.kind, EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))),
+        Err(error) => { warn!("Folder watcher error: {}", error); false }
+    }
+}